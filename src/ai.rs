@@ -4,9 +4,12 @@ use raylib::prelude::Color;
 use crate::map::Map;
 use crate::types::{Enemy, EnemyState, Player, Decoration, Chest};
 use crate::audio::Audio; 
+use crate::screen_fx::ScreenFx;
+use crate::collision::{move_circle, apply_knockback};
+use crate::consts::{ENEMY_RADIUS, GRENADE_FLEE_RADIUS};
 
 /// Línea de visión: DDA sobre la rejilla de paredes.
-/// Devuelve true si no hay una pared entre (sx,sy) y (tx,ty).
+/// Devuelve true si no hay una pared opaca entre (sx,sy) y (tx,ty).
 pub fn has_los(map: &Map, sx: f32, sy: f32, tx: f32, ty: f32) -> bool {
     let dx = tx - sx;
     let dy = ty - sy;
@@ -33,6 +36,9 @@ pub fn has_los(map: &Map, sx: f32, sy: f32, tx: f32, ty: f32) -> bool {
 
     let target_cell_x = tx.floor() as i32;
     let target_cell_y = ty.floor() as i32;
+    if map_x == target_cell_x && map_y == target_cell_y {
+        return true; // misma celda: no hay pared en medio
+    }
 
     // Límite de pasos razonable
     let max_steps = (map.w + map.h) * 4;
@@ -50,8 +56,8 @@ pub fn has_los(map: &Map, sx: f32, sy: f32, tx: f32, ty: f32) -> bool {
             return true;
         }
 
-        // ¿Pared bloqueando?
-        if map.at(map_x, map_y) == 1 {
+        // ¿Pared bloqueando? (rejillas/ventanas dejan ver y disparar)
+        if map.blocks_sight(map_x, map_y) {
             return false;
         }
     }
@@ -59,8 +65,9 @@ pub fn has_los(map: &Map, sx: f32, sy: f32, tx: f32, ty: f32) -> bool {
 }
 
 /// Aplica daño al jugador: primero escudo, luego vida.
+/// Avisa a `fx` (viñeta/flash/arco hacia `from`).
 /// Devuelve (hubo_daño, murió_en_esta_llamada)
-fn apply_damage(player: &mut Player, mut dmg: i32) -> (bool, bool) {
+pub fn apply_damage(player: &mut Player, mut dmg: i32, fx: &mut ScreenFx, from: (f32, f32)) -> (bool, bool) {
     if dmg <= 0 { return (false, false); }
 
     let hp_before = player.hp;
@@ -78,6 +85,9 @@ fn apply_damage(player: &mut Player, mut dmg: i32) -> (bool, bool) {
         if player.hp < 0 { player.hp = 0; }
     }
 
+    let to_attacker = (from.1 - player.y).atan2(from.0 - player.x);
+    fx.on_player_damage(hp_before - player.hp, sh_before - player.shield, to_attacker);
+
     let took = (player.hp != hp_before) || (player.shield != sh_before);
     let died_now = was_alive && player.hp <= 0;
     (took, died_now)
@@ -92,7 +102,7 @@ pub struct AiCfg {
 }
 
 /// Actualiza todos los enemigos: movimiento, colisiones, melee y disparo.
-/// - Respeta colisiones contra paredes y decoraciones bloqueantes.
+/// - Respeta colisiones contra paredes, decoraciones bloqueantes y cofres.
 /// - Disparo usa daño/cadencia del arma de cada enemigo.
 /// - Cambia color para feedback (amarillo al disparar/recibir daño, rosa herido, gris muerto).
 pub fn update_enemies(
@@ -103,7 +113,9 @@ pub fn update_enemies(
     map: &Map,
     decorations: &[Decoration],
     chests: &[Chest],
+    grenades: &[(f32, f32)], // granadas ya en el suelo (de las que se huye)
     audio: &Audio, 
+    fx: &mut ScreenFx,
 ) {
     // Orden opcional (útil para render si reutilizas el orden): más lejos primero.
     enemies.sort_by(|a, b| {
@@ -117,6 +129,9 @@ pub fn update_enemies(
         e.weapon_cd = (e.weapon_cd - dt).max(0.0);
         e.flash_timer = (e.flash_timer - dt).max(0.0);
 
+        // Empuje de explosiones (también a los cuerpos)
+        (e.x, e.y) = apply_knockback(map, decorations, chests, e.x, e.y, &mut e.knock, ENEMY_RADIUS, dt);
+
         // Normalizar estado por vida
         if e.hp <= 0 {
     if e.state != EnemyState::Dead {
@@ -141,53 +156,34 @@ if e.state == EnemyState::Dead {
             e.state = EnemyState::Chase;
         }
 
+        // Granada en el suelo, cerca y a la vista: la más próxima (vector enemigo - granada).
+        // Verla también lo pone alerta
+        let threat = grenades.iter()
+            .map(|&(gx, gy)| (e.x - gx, e.y - gy, gx, gy))
+            .filter(|&(ax, ay, gx, gy)| ax.hypot(ay) < GRENADE_FLEE_RADIUS && has_los(map, gx, gy, e.x, e.y))
+            .min_by(|a, b| a.0.hypot(a.1).partial_cmp(&b.0.hypot(b.1)).unwrap_or(Ordering::Equal))
+            .map(|(ax, ay, _, _)| (ax, ay));
+        if threat.is_some() && e.state == EnemyState::Idle {
+            e.state = EnemyState::Chase;
+        }
+
         if e.state == EnemyState::Chase {
-            // Dirección normalizada (si dist=0, no mueve)
-            let (dir_x, dir_y) = if dist > 1e-4 {
-                (dx / dist, dy / dist)
+            // Se aleja de la granada o va hacia el jugador (dirección normalizada; si la distancia es 0, no mueve)
+            let (tx, ty, td) = match threat {
+                Some((ax, ay)) => (ax, ay, ax.hypot(ay)),
+                None => (dx, dy, dist),
+            };
+            let (dir_x, dir_y) = if td > 1e-4 {
+                (tx / td, ty / td)
             } else {
                 (0.0, 0.0)
             };
 
-            // Movimiento propuesto
-            let mut mvx = dir_x * e.speed * dt;
-            let mut mvy = dir_y * e.speed * dt;
-
-            // Colisión simple (AABB por ejes y radio)
-            let radius = 0.2_f32;
-
-            // Helper: colisión contra decoraciones bloqueantes
-            let collides_deco = |x: f32, y: f32| -> bool {
-                decorations.iter().any(|d| {
-                    if !d.is_blocking() { return false; }
-                    let dx = d.x - x;
-                    let dy = d.y - y;
-                    let rr = radius + d.radius;
-                    (dx * dx + dy * dy) < (rr * rr)
-                })
-            };
-
-            // Avance eje X
-            let next_x = e.x + mvx;
-            let can_x = map.at((next_x - radius).floor() as i32, e.y.floor() as i32) == 0
-                && map.at((next_x + radius).floor() as i32, e.y.floor() as i32) == 0
-                && !collides_deco(next_x, e.y);
-            if can_x {
-                e.x = next_x;
-            } else {
-                mvx = 0.0;
-            }
-
-            // Avance eje Y
-            let next_y = e.y + mvy;
-            let can_y = map.at(e.x.floor() as i32, (next_y - radius).floor() as i32) == 0
-                && map.at(e.x.floor() as i32, (next_y + radius).floor() as i32) == 0
-                && !collides_deco(e.x, next_y);
-            if can_y {
-                e.y = next_y;
-            } else {
-                mvy = 0.0;
-            }
+            // Movimiento propuesto, con la misma colisión que el jugador
+            // (círculo vs rejilla/decoraciones/cofres, deslizando por paredes)
+            let mvx = dir_x * e.speed * dt;
+            let mvy = dir_y * e.speed * dt;
+            (e.x, e.y) = move_circle(map, decorations, chests, e.x, e.y, mvx, mvy, ENEMY_RADIUS);
 
             // Recalcular distancia tras mover
             let ndx = player.x - e.x;
@@ -203,7 +199,7 @@ if e.state == EnemyState::Dead {
 
             // Melee si está muy cerca
 if ndist < cfg.melee_range {
-    let (took, died_now) = apply_damage(player, (cfg.melee_dps * dt) as i32);
+    let (took, died_now) = apply_damage(player, (cfg.melee_dps * dt) as i32, fx, (e.x, e.y));
     if died_now {
         audio.play_player_death();
     } else if took {
@@ -213,7 +209,7 @@ if ndist < cfg.melee_range {
 
 // Disparo si está dentro de rango y con LOS y cooldown listo
 if ndist <= cfg.shoot_range && e.weapon_cd <= 0.0 && has_los(map, e.x, e.y, player.x, player.y) {
-    let (took, died_now) = apply_damage(player, e.weapon.damage);
+    let (took, died_now) = apply_damage(player, e.weapon.shot_damage(), fx, (e.x, e.y)); // sin dispersión: el disparo entero
     e.weapon_cd = e.weapon.fire_interval;
     e.flash_timer = 0.08;
 
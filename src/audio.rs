@@ -1,6 +1,7 @@
 use std::sync::OnceLock;
 use raylib::core::audio::{RaylibAudio, Sound, Music};
 use crate::types::WeaponType;
+use crate::weapons::WeaponCatalog;
 
 
 // Dispositivo global (una sola instancia)
@@ -19,16 +20,11 @@ pub struct Audio {
     ra: &'static RaylibAudio,
     // --- SFX existentes ---
     pub snd_consume:  Sound<'static>,
-    pub snd_pistol:   Sound<'static>,
-    pub snd_smg:      Sound<'static>,
-    pub snd_rifle:    Sound<'static>,
-    pub snd_shotgun:  Sound<'static>,
-    pub snd_rocket:   Sound<'static>,
-    pub snd_reload_pistol:   Sound<'static>,
-    pub snd_reload_smg:      Sound<'static>,
-    pub snd_reload_rifle:    Sound<'static>,
-    pub snd_reload_shotgun:  Sound<'static>,
-    pub snd_reload_rocket:   Sound<'static>,
+    // disparo / recarga de cada arma del catálogo (índice = `WeaponType`)
+    snd_shots:   Vec<Sound<'static>>,
+    snd_reloads: Vec<Sound<'static>>,
+    pub snd_explosion: Sound<'static>,
+    pub snd_swing:     Sound<'static>, // golpe cuerpo a cuerpo (silbido)
     // --- NUEVO: sonidos del jugador ---
     pub snd_player_hurt:  Sound<'static>,
     pub snd_player_death: Sound<'static>,
@@ -40,23 +36,18 @@ pub struct Audio {
 }
 
 impl Audio {
-    pub fn new() -> Self {
+    pub fn new(weapons: &WeaponCatalog) -> Self {
         let ra = device();
 
         // Ajusta rutas si usas otras
         let snd_consume = ra.new_sound("assets/sfx/consume.wav").expect("Falta consume.wav");
 
-        let snd_pistol  = ra.new_sound("assets/sfx/weapons/pistol.wav").expect("Falta pistol.wav");
-        let snd_smg     = ra.new_sound("assets/sfx/weapons/smg.wav").expect("Falta smg.wav");
-        let snd_rifle   = ra.new_sound("assets/sfx/weapons/rifle.wav").expect("Falta rifle.wav");
-        let snd_shotgun = ra.new_sound("assets/sfx/weapons/shotgun.wav").expect("Falta shotgun.wav");
-        let snd_rocket  = ra.new_sound("assets/sfx/weapons/rocket.wav").expect("Falta rocket.wav");
-
-        let snd_reload_pistol  = ra.new_sound("assets/sfx/weapons/reload_pistol.wav").expect("Falta reload_pistol.wav");
-        let snd_reload_smg     = ra.new_sound("assets/sfx/weapons/reload_smg.wav").expect("Falta reload_smg.wav");
-        let snd_reload_rifle   = ra.new_sound("assets/sfx/weapons/reload_rifle.wav").expect("Falta reload_rifle.wav");
-        let snd_reload_shotgun = ra.new_sound("assets/sfx/weapons/reload_shotgun.wav").expect("Falta reload_shotgun.wav");
-        let snd_reload_rocket  = ra.new_sound("assets/sfx/weapons/reload_rocket.wav").expect("Falta reload_rocket.wav");
+        // Armas: rutas del catálogo
+        let load = |path: &str| ra.new_sound(path).unwrap_or_else(|_| panic!("Falta {path}"));
+        let snd_shots   = weapons.iter().map(|d| load(&d.shot_sound)).collect();
+        let snd_reloads = weapons.iter().map(|d| load(&d.reload_sound)).collect();
+        let snd_explosion = ra.new_sound("assets/sfx/weapons/explosion.wav").expect("Falta explosion.wav");
+        let snd_swing     = ra.new_sound("assets/sfx/weapons/swing.wav").expect("Falta swing.wav");
 
         // 🔊 NUEVO: daño/muerte del jugador
         let snd_player_hurt  = ra.new_sound("assets/sfx/hurt.wav").expect("Falta player/hurt.wav");
@@ -74,8 +65,8 @@ impl Audio {
         Self {
             ra,
             snd_consume,
-            snd_pistol, snd_smg, snd_rifle, snd_shotgun, snd_rocket,
-            snd_reload_pistol, snd_reload_smg, snd_reload_rifle, snd_reload_shotgun, snd_reload_rocket,
+            snd_shots, snd_reloads,
+            snd_explosion, snd_swing,
             snd_player_hurt, snd_player_death, snd_enemy_hurt,
             snd_enemy_death, music_game,
         }
@@ -84,23 +75,13 @@ impl Audio {
     pub fn set_master(&self, v: f32) { self.ra.set_master_volume(v.clamp(0.0, 1.0)); }
     pub fn play_consume(&self) { self.snd_consume.play(); }
     pub fn play_shot(&self, wtype: WeaponType) {
-        match wtype {
-            WeaponType::Pistol => self.snd_pistol.play(),
-            WeaponType::SMG => self.snd_smg.play(),
-            WeaponType::Rifle => self.snd_rifle.play(),
-            WeaponType::Shotgun => self.snd_shotgun.play(),
-            WeaponType::RocketLauncher => self.snd_rocket.play(),
-        }
+        if let Some(snd) = self.snd_shots.get(wtype.0) { snd.play(); }
     }
     pub fn play_reload(&self, wtype: WeaponType) {
-        match wtype {
-            WeaponType::Pistol => self.snd_reload_pistol.play(),
-            WeaponType::SMG => self.snd_reload_smg.play(),
-            WeaponType::Rifle => self.snd_reload_rifle.play(),
-            WeaponType::Shotgun => self.snd_reload_shotgun.play(),
-            WeaponType::RocketLauncher => self.snd_reload_rocket.play(),
-        }
+        if let Some(snd) = self.snd_reloads.get(wtype.0) { snd.play(); }
     }
+    pub fn play_explosion(&self) { self.snd_explosion.play(); }
+    pub fn play_swing(&self) { self.snd_swing.play(); }
     // --- NUEVO: hooks de daño del jugador ---
     pub fn play_player_hurt(&self)  { self.snd_player_hurt.play(); }
     pub fn play_player_death(&self) { self.snd_player_death.play(); }
@@ -0,0 +1,76 @@
+use crate::map::Map;
+use crate::types::{Player, Enemy, Pickup, Chest, Checkpoint};
+use crate::consts::CHECKPOINT_RADIUS;
+
+/// Foto de la partida al tocar un checkpoint (en memoria, no se guarda a disco)
+struct RunSnapshot {
+    player: Player,       // posición, vida, escudo, slots, munición…
+    enemies: Vec<Enemy>,  // vida/estado (el Vec se reordena cada frame: se guarda entero)
+    pickups: Vec<Pickup>, // los que quedaban en el suelo
+    chests_opened: Vec<bool>,
+    kills: u32,
+    headshots: u32,
+}
+
+/// Checkpoints del mapa + la última foto guardada
+pub struct Checkpoints {
+    pub list: Vec<Checkpoint>,
+    snapshot: Option<RunSnapshot>,
+}
+
+impl Checkpoints {
+    pub fn new(map: &Map) -> Self {
+        Self {
+            list: map.checkpoint_spawns.iter().map(|&(x, y)| Checkpoint { x, y, active: false }).collect(),
+            snapshot: None,
+        }
+    }
+
+    /// Si el jugador toca un checkpoint que no es el activo, lo activa y guarda la foto.
+    /// Devuelve el índice del checkpoint recién activado.
+    pub fn update(&mut self, player: &Player, enemies: &[Enemy], pickups: &[Pickup], chests: &[Chest], kills: u32, headshots: u32) -> Option<usize> {
+        let touched = self.list.iter().position(|c| {
+            !c.active && (c.x - player.x).powi(2) + (c.y - player.y).powi(2) <= CHECKPOINT_RADIUS * CHECKPOINT_RADIUS
+        })?;
+
+        for (i, c) in self.list.iter_mut().enumerate() {
+            c.active = i == touched;
+        }
+        self.snapshot = Some(RunSnapshot {
+            player: player.clone(),
+            enemies: enemies.to_vec(),
+            pickups: pickups.to_vec(),
+            chests_opened: chests.iter().map(|c| c.opened).collect(),
+            kills,
+            headshots,
+        });
+        Some(touched)
+    }
+
+    pub fn has_snapshot(&self) -> bool {
+        self.snapshot.is_some()
+    }
+
+    /// Vuelve la partida a la última foto. El jugador reaparece quieto y de pie.
+    pub fn restore(&self, player: &mut Player, enemies: &mut Vec<Enemy>, pickups: &mut Vec<Pickup>, chests: &mut [Chest], kills: &mut u32, headshots: &mut u32) -> bool {
+        let Some(snap) = &self.snapshot else { return false };
+
+        *player = snap.player.clone();
+        player.pitch = 0.0;
+        player.jump_z = 0.0;
+        player.vz = 0.0;
+        player.crouch = 0.0;
+        player.sprinting = false;
+        player.move_mods.clear();
+        player.knock = (0.0, 0.0);
+
+        *enemies = snap.enemies.clone();
+        *pickups = snap.pickups.clone();
+        for (c, &opened) in chests.iter_mut().zip(&snap.chests_opened) {
+            c.opened = opened;
+        }
+        *kills = snap.kills;
+        *headshots = snap.headshots;
+        true
+    }
+}
@@ -0,0 +1,117 @@
+use crate::map::Map;
+use crate::types::{Decoration, Chest};
+use crate::consts::KNOCKBACK_DAMP;
+
+// Iteraciones de empuje por paso (esquinas / varios obstáculos a la vez)
+const RESOLVE_ITERS: usize = 3;
+
+/// Mueve un círculo de radio `radius` desde (x, y) por (dx, dy) y devuelve la
+/// posición final. Colisiona contra las celdas sólidas del mapa (círculo vs
+/// rejilla), decoraciones bloqueantes y cofres (círculo vs círculo).
+/// Tras mover, se empuja el círculo fuera de lo que pise por la normal de
+/// contacto: así se desliza por las paredes y redondea las esquinas.
+/// Compartido por el jugador y los enemigos.
+pub fn move_circle(
+    map: &Map,
+    decorations: &[Decoration],
+    chests: &[Chest],
+    x: f32, y: f32,
+    dx: f32, dy: f32,
+    radius: f32,
+) -> (f32, f32) {
+    // Sub-pasos para no atravesar paredes con un dt grande
+    let dist = (dx * dx + dy * dy).sqrt();
+    let steps = ((dist / (radius * 0.5)).ceil() as usize).max(1);
+    let (sx, sy) = (dx / steps as f32, dy / steps as f32);
+
+    let (mut px, mut py) = (x, y);
+    for _ in 0..steps {
+        let (prev_x, prev_y) = (px, py);
+        px += sx;
+        py += sy;
+
+        for _ in 0..RESOLVE_ITERS {
+            let mut pushed = false;
+            pushed |= push_out_of_grid(map, &mut px, &mut py, radius);
+            for d in decorations.iter().filter(|d| d.is_blocking()) {
+                pushed |= push_out_of_circle(&mut px, &mut py, radius, d.x, d.y, d.radius);
+            }
+            for c in chests.iter().filter(|c| c.is_blocking()) {
+                pushed |= push_out_of_circle(&mut px, &mut py, radius, c.x, c.y, c.radius);
+            }
+            if !pushed { break; }
+        }
+
+        // Si aun así quedó el centro dentro de una pared, se deshace el paso
+        if map.at(px.floor() as i32, py.floor() as i32) != 0 {
+            px = prev_x;
+            py = prev_y;
+            break;
+        }
+    }
+    (px, py)
+}
+
+/// Empuje de explosiones: mueve el círculo con `knock` (celdas/s) usando la
+/// misma colisión que `move_circle` y lo va frenando. Devuelve la posición final.
+pub fn apply_knockback(
+    map: &Map,
+    decorations: &[Decoration],
+    chests: &[Chest],
+    x: f32, y: f32,
+    knock: &mut (f32, f32),
+    radius: f32,
+    dt: f32,
+) -> (f32, f32) {
+    if *knock == (0.0, 0.0) { return (x, y); }
+    let pos = move_circle(map, decorations, chests, x, y, knock.0 * dt, knock.1 * dt, radius);
+    let k = (-KNOCKBACK_DAMP * dt).exp();
+    *knock = (knock.0 * k, knock.1 * k);
+    if knock.0.hypot(knock.1) < 0.05 { *knock = (0.0, 0.0); }
+    pos
+}
+
+/// Punto de la celda (cx, cy) más cercano a (x, y)
+fn closest_in_cell(x: f32, y: f32, cx: i32, cy: i32) -> (f32, f32) {
+    (x.clamp(cx as f32, cx as f32 + 1.0), y.clamp(cy as f32, cy as f32 + 1.0))
+}
+
+/// Círculo vs celdas sólidas vecinas: empuja por la normal del punto más cercano.
+fn push_out_of_grid(map: &Map, px: &mut f32, py: &mut f32, radius: f32) -> bool {
+    let mut pushed = false;
+    let (x0, x1) = ((*px - radius).floor() as i32, (*px + radius).floor() as i32);
+    let (y0, y1) = ((*py - radius).floor() as i32, (*py + radius).floor() as i32);
+    for cy in y0..=y1 {
+        for cx in x0..=x1 {
+            if map.at(cx, cy) == 0 { continue; }
+            let (nx, ny) = closest_in_cell(*px, *py, cx, cy);
+            let (ox, oy) = (*px - nx, *py - ny);
+            let d2 = ox * ox + oy * oy;
+            if d2 >= radius * radius || d2 < 1e-12 { continue; }
+            let d = d2.sqrt();
+            let k = (radius - d) / d;
+            *px += ox * k;
+            *py += oy * k;
+            pushed = true;
+        }
+    }
+    pushed
+}
+
+/// Círculo vs círculo
+fn push_out_of_circle(px: &mut f32, py: &mut f32, radius: f32, ox: f32, oy: f32, oradius: f32) -> bool {
+    let (dx, dy) = (*px - ox, *py - oy);
+    let rr = radius + oradius;
+    let d2 = dx * dx + dy * dy;
+    if d2 >= rr * rr { return false; }
+    let d = d2.sqrt();
+    if d < 1e-6 {
+        // encima exacto: empujar en cualquier dirección
+        *px += rr;
+        return true;
+    }
+    let k = (rr - d) / d;
+    *px += dx * k;
+    *py += dy * k;
+    true
+}
@@ -1,7 +1,99 @@
 use raylib::prelude::*;
 
+// Tamaño inicial de la ventana (luego se puede redimensionar / pantalla completa)
 pub const SCREEN_W: i32 = 1280;
 pub const SCREEN_H: i32 = 720;
+// Tamaño mínimo de la ventana al redimensionar
+pub const MIN_SCREEN_W: i32 = 640;
+pub const MIN_SCREEN_H: i32 = 360;
+
+// Vista 3D: alto interno de fábrica (el ancho sale del aspecto de la ventana) y
+// filtro al escalarla (true = bilinear, false = nearest). Se cambian en VIDEO_PATH
+pub const VIEW_INTERNAL_H: i32 = 360;
+pub const VIEW_SMOOTH: bool = false;
+pub const VIDEO_PATH: &str = "assets/video.txt";
+
+// Paredes “see-through” (textura con huecos): el rayo sigue detrás de ellas,
+// se ve y se dispara a través, pero bloquean el paso
+pub const WALL_GRATE:  u8 = 8;  // '8' rejilla
+pub const WALL_FENCE:  u8 = 9;  // '9' valla de alambre
+pub const WALL_WINDOW: u8 = 10; // 'V' ventana
+pub const SEE_THROUGH_WALLS: [u8; 3] = [WALL_GRATE, WALL_FENCE, WALL_WINDOW];
+
+// Marcas de impacto en paredes: máximo en pantalla y cuántas de las más
+// viejas se van desvaneciendo antes de desaparecer
+pub const DECAL_MAX:  usize = 96;
+pub const DECAL_FADE: usize = 24;
+
+// Tope de partículas vivas (las más viejas se descartan)
+pub const PARTICLE_MAX: usize = 1024;
+
+// Radios de colisión (celdas)
+pub const PLAYER_RADIUS: f32 = 0.2;
+pub const ENEMY_RADIUS:  f32 = 0.2;
+
+// Cohetes: velocidad (celdas/s), radio de choque y vida máxima en vuelo (s)
+pub const ROCKET_SPEED:  f32 = 8.0;
+pub const ROCKET_RADIUS: f32 = 0.08;
+pub const ROCKET_LIFE:   f32 = 4.0;
+
+// Explosiones: radio del daño en área, fracción del daño que se come el propio
+// jugador, empuje máximo (celdas/s) y cuánto se frena el empuje (1/s)
+pub const SPLASH_RADIUS:    f32 = 2.2;
+pub const SPLASH_SELF_MULT: f32 = 0.5;
+pub const SPLASH_KNOCKBACK: f32 = 7.0;
+pub const KNOCKBACK_DAMP:   f32 = 6.0;
+
+// Granadas: mecha (s), daño en el centro, velocidad de lanzamiento (celdas/s) sin
+// cargar y a carga completa, tiempo hasta la carga completa y cuánto sube el tiro
+pub const GRENADE_FUSE:        f32 = 2.5;
+pub const GRENADE_DAMAGE:      i32 = 110;
+pub const GRENADE_MIN_SPEED:   f32 = 2.5;
+pub const GRENADE_MAX_SPEED:   f32 = 8.0;
+pub const GRENADE_CHARGE_TIME: f32 = 1.0;
+pub const GRENADE_LOFT:        f32 = 0.35; // vz inicial = velocidad × esto (+ pitch)
+// Física: radio, gravedad (alturas de pared/s²), rebote (fracción de velocidad
+// que conserva) y rozamiento con el suelo (1/s)
+pub const GRENADE_RADIUS:   f32 = 0.06;
+pub const GRENADE_GRAVITY:  f32 = 9.0;
+pub const GRENADE_BOUNCE:   f32 = 0.45;
+pub const GRENADE_FRICTION: f32 = 3.0;
+// Los enemigos que ven una granada en el suelo a menos de esto huyen de ella
+pub const GRENADE_FLEE_RADIUS: f32 = 3.0;
+
+// Velocidad (×) mientras se canaliza un consumible
+pub const CONSUME_MOVE_MULT: f32 = 0.5;
+
+// Archivo de controles (acción = tecla[, tecla…]); si falta se usan los de fábrica
+pub const CONTROLS_PATH: &str = "assets/controls.txt";
+pub const WEAPONS_PATH: &str = "assets/weapons.txt"; // catálogo de armas (stats, assets, dónde salen)
+
+// Mando: índice del gamepad que se lee y zona muerta de los sticks (0..1)
+pub const PAD_INDEX: i32 = 0;
+pub const PAD_DEADZONE: f32 = 0.2;
+
+// Muerte: la cámara cae hasta DEATH_EYE_Z (mirando hacia DEATH_PITCH)
+// y tras DEATH_CAM_TIME segundos aparece la pantalla de game over
+pub const DEATH_CAM_TIME: f32 = 2.0;
+pub const DEATH_EYE_Z: f32 = 0.08;
+pub const DEATH_PITCH: f32 = 0.12;
+
+// Precisión: el cono extra por disparos seguidos se cierra a BLOOM_RECOVERY rad/s;
+// agachado el cono se multiplica por CROUCH_SPREAD_MULT y en el aire cuenta como AIR_SPREAD_MOVE
+// (en unidades de `bob_amp`: 1 = andar)
+pub const BLOOM_RECOVERY: f32 = 0.06;
+pub const BLOOM_MAX_DEFAULT: f32 = 4.0; // `bloom_max` que no viene en weapons.txt = bloom × esto
+pub const CROUCH_SPREAD_MULT: f32 = 0.6;
+pub const AIR_SPREAD_MOVE: f32 = 1.5;
+
+// Multiplicadores de daño del hitscan según dónde entra la bala (ver `HitZones`)
+pub const HEAD_DAMAGE_MULT: f32 = 2.0;
+pub const TORSO_DAMAGE_MULT: f32 = 1.0;
+pub const LEGS_DAMAGE_MULT: f32 = 0.75;
+
+// Checkpoints: radio para activarlos y vidas (reapariciones) por partida
+pub const CHECKPOINT_RADIUS: f32 = 0.6;
+pub const PLAYER_LIVES: u32 = 3;
 
 pub const PLAYER_MAX_HP: i32 = 100;
 pub const PLAYER_MAX_SHIELD: i32 = 100;
@@ -21,6 +113,7 @@ pub const MAX_STACK_HEALTH_SMALL: i32 = 15;
 pub const MAX_STACK_HEALTH_BIG:   i32 = 3;
 pub const MAX_STACK_SHIELD_SMALL: i32 = 6;
 pub const MAX_STACK_SHIELD_BIG:   i32 = 3;
+pub const MAX_STACK_GRENADE:      i32 = 4;
 
 // Cooldowns (segundos) por consumible
 pub const CD_HEALTH_SMALL: f32 = 2.5;
@@ -51,9 +144,27 @@ pub const P_RARITY_LEGENDARY:  f32 = 0.03;
 
 
 // ===== Melee / Puño =====
+// Puño (mano vacía) y golpe rápido con un arma de fuego en la mano
 pub const PUNCH_DAMAGE: i32   = 25;   // daño del golpe
 pub const PUNCH_RANGE: f32    = 1.4;  // alcance efectivo (metros/celdas)
 pub const PUNCH_COOLDOWN: f32 = 0.6;  // tiempo entre golpes (s)
+pub const PUNCH_ARC: f32      = 0.7;  // ancho del arco delante del jugador (rad)
+
+// Cuchillo: rápido y estrecho
+pub const KNIFE_DAMAGE: i32   = 45;
+pub const KNIFE_RANGE: f32    = 1.3;
+pub const KNIFE_SWING: f32    = 0.35;
+pub const KNIFE_ARC: f32      = 0.6;
+
+// Bate: lento, ancho y empuja
+pub const BAT_DAMAGE: i32     = 70;
+pub const BAT_RANGE: f32      = 1.7;
+pub const BAT_SWING: f32      = 0.8;
+pub const BAT_ARC: f32        = 1.6;
+pub const BAT_KNOCKBACK: f32  = 4.0;  // celdas/s de empuje a cada enemigo golpeado
+pub const MELEE_COLOR: Color  = Color::new(200, 150, 100, 255); // color de sus pickups (sin textura)
+pub const GRENADE_COLOR: Color = Color::new(90, 120, 60, 255);  // color de su pickup y de la granada si falta la textura
+pub const GRENADE_PACK: i32   = 2; // granadas por pickup de cofre o del mapa
 
 // --- Spawns aleatorios de munición ---
 pub const P_AMMO_NONE:   f32 = 0.35; // no aparece nada
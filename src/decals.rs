@@ -0,0 +1,67 @@
+use std::collections::VecDeque;
+use raylib::prelude::Color;
+
+use crate::consts::{DECAL_MAX, DECAL_FADE};
+use crate::types::{WallFace, WallHit};
+
+/// Tipo de marca en la pared
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DecalKind {
+    BulletHole, // agujero de bala
+    Scorch,     // quemadura de cohete
+}
+
+impl DecalKind {
+    /// Radio en espacio de textura (1.0 = ancho de la cara)
+    pub fn radius(self) -> f32 {
+        match self {
+            DecalKind::BulletHole => 0.03,
+            DecalKind::Scorch     => 0.22,
+        }
+    }
+    /// (color del borde, color del centro)
+    pub fn colors(self) -> (Color, Color) {
+        match self {
+            DecalKind::BulletHole => (Color::new(40, 35, 30, 200), Color::new(10, 10, 10, 255)),
+            DecalKind::Scorch     => (Color::new(20, 15, 10, 90),  Color::new(15, 10, 5, 200)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Decal {
+    pub face: WallFace,
+    pub u: f32,
+    pub v: f32,
+    pub kind: DecalKind,
+}
+
+/// Ring buffer de marcas: al llenarse se descarta la más vieja,
+/// y las más cercanas a ser descartadas se van desvaneciendo.
+pub struct Decals {
+    items: VecDeque<Decal>,
+}
+
+impl Decals {
+    pub fn new() -> Self {
+        Self { items: VecDeque::with_capacity(DECAL_MAX) }
+    }
+
+    pub fn add(&mut self, hit: WallHit, kind: DecalKind) {
+        if self.items.len() >= DECAL_MAX {
+            self.items.pop_front();
+        }
+        self.items.push_back(Decal { face: hit.face, u: hit.u, v: hit.v.clamp(0.0, 1.0), kind });
+    }
+
+    /// Marcas de una cara con su opacidad (0..1) según su turno de salida del buffer.
+    pub fn on_face(&self, face: WallFace) -> impl Iterator<Item = (&Decal, f32)> + '_ {
+        let free = DECAL_MAX - self.items.len();
+        self.items.iter().enumerate()
+            .filter(move |(_, dc)| dc.face == face)
+            .map(move |(i, dc)| {
+                let left = free + i + 1; // cuántas marcas nuevas faltan para que salga
+                (dc, (left as f32 / DECAL_FADE as f32).min(1.0))
+            })
+    }
+}
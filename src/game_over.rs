@@ -0,0 +1,161 @@
+use raylib::prelude::*;
+use raylib::consts::MouseButton;
+use crate::types::Viewport;
+use crate::input::{Action, Bindings, CHOICE_HELP};
+
+/// Qué hacer desde la pantalla de game over
+pub enum GameOverAction {
+    None,
+    Retry,      // reintentar el nivel desde el principio
+    Checkpoint, // volver al último checkpoint
+    ToMenu,     // volver al menú
+}
+
+// Botones de izquierda a derecha
+const BTN_RETRY: usize = 0;
+const BTN_CHECKPOINT: usize = 1;
+const BTN_MENU: usize = 2;
+
+pub struct GameOverScreen {
+    hovered: Option<usize>,
+    focus: usize, // botón con foco para teclado/mando (←/→ lo mueven)
+    // stats que mostramos
+    elapsed_secs: f32,
+    kills: u32,
+    headshots: u32,
+    has_checkpoint: bool, // sin checkpoint el botón sale apagado
+}
+
+impl GameOverScreen {
+    pub fn new() -> Self {
+        Self {
+            hovered: None,
+            focus: BTN_RETRY,
+            elapsed_secs: 0.0,
+            kills: 0,
+            headshots: 0,
+            has_checkpoint: false,
+        }
+    }
+
+    pub fn set_stats(&mut self, elapsed_secs: f32, kills: u32, headshots: u32, has_checkpoint: bool) {
+        self.elapsed_secs = elapsed_secs.max(0.0);
+        self.kills = kills;
+        self.headshots = headshots;
+        self.has_checkpoint = has_checkpoint;
+        self.focus = if has_checkpoint { BTN_CHECKPOINT } else { BTN_RETRY };
+    }
+
+    fn enabled(&self, btn: usize) -> bool {
+        btn != BTN_CHECKPOINT || self.has_checkpoint
+    }
+
+    /// Tres botones en fila, centrados en el tercio inferior
+    fn btn_rect(&self, vp: Viewport, btn: usize) -> (i32, i32, i32, i32) {
+        let w = 230; let h = 56; let gap = 20;
+        let total = 3 * w + 2 * gap;
+        let x = (vp.w - total) / 2 + btn as i32 * (w + gap);
+        let y = (vp.h * 2 / 3) - h/2;
+        (x, y, w, h)
+    }
+
+    /// Manejar entrada (teclas/ratón/mando). Llamar **antes** del begin_drawing.
+    pub fn handle_input(&mut self, rl: &RaylibHandle, bindings: &Bindings) -> GameOverAction {
+        let m = rl.get_mouse_position();
+        let vp = Viewport::of(rl);
+
+        self.hovered = (0..3).find(|&b| {
+            let (x, y, w, h) = self.btn_rect(vp, b);
+            self.enabled(b) && m.x >= x as f32 && m.x <= (x+w) as f32 && m.y >= y as f32 && m.y <= (y+h) as f32
+        });
+        if let Some(b) = self.hovered { self.focus = b; }
+
+        // ←/→ saltan el botón de checkpoint si no hay
+        if bindings.pressed(rl, Action::MenuLeft) {
+            self.focus = (0..self.focus).rev().find(|&b| self.enabled(b)).unwrap_or(self.focus);
+        }
+        if bindings.pressed(rl, Action::MenuRight) {
+            self.focus = (self.focus + 1..3).find(|&b| self.enabled(b)).unwrap_or(self.focus);
+        }
+
+        let clicked = rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) && self.hovered.is_some();
+        if clicked || bindings.pressed(rl, Action::MenuConfirm) {
+            return match self.focus {
+                BTN_RETRY => GameOverAction::Retry,
+                BTN_CHECKPOINT => GameOverAction::Checkpoint,
+                BTN_MENU => GameOverAction::ToMenu,
+                _ => GameOverAction::None,
+            };
+        }
+        if bindings.pressed(rl, Action::MenuBack) {
+            return GameOverAction::ToMenu;
+        }
+
+        GameOverAction::None
+    }
+
+    /// Dibujo de la pantalla de game over. Llamar **dentro** del begin_drawing.
+    pub fn draw(&self, d: &mut RaylibDrawHandle, bindings: &Bindings) {
+        let vp = Viewport::of(d);
+        let sw = vp.w;
+
+        // Fondo
+        d.draw_rectangle_gradient_v(
+            0, 0, vp.w, vp.h,
+            Color::new(40, 10, 10, 255),
+            Color::new(90, 25, 25, 255),
+        );
+
+        // Título
+        let title = "HAS MUERTO";
+        let fs_title = 56;
+        let tw = d.measure_text(title, fs_title);
+        d.draw_text(title, (sw - tw)/2 + 2, 100 + 2, fs_title, Color::new(0,0,0,160));
+        d.draw_text(title, (sw - tw)/2,     100,     fs_title, Color::WHITE);
+
+        // Stats
+        let mins = (self.elapsed_secs as u32) / 60;
+        let secs = (self.elapsed_secs as u32) % 60;
+        let time_txt = format!("Sobreviviste: {}:{:02}", mins, secs);
+        let kills_txt = format!("Eliminaciones: {} ({} a la cabeza)", self.kills, self.headshots);
+        let fs_stats = 24;
+
+        let tw_time  = d.measure_text(&time_txt, fs_stats);
+        let tw_kills = d.measure_text(&kills_txt, fs_stats);
+        d.draw_text(&time_txt,  (sw - tw_time)/2,  180, fs_stats, Color::new(235,235,235,240));
+        d.draw_text(&kills_txt, (sw - tw_kills)/2, 210, fs_stats, Color::new(235,235,235,240));
+
+        // Botones
+        let menu_txt = format!("MENÚ ({})", bindings.label(Action::MenuBack));
+        let labels = ["REINTENTAR", "CHECKPOINT", menu_txt.as_str()];
+        for (b, label) in labels.iter().enumerate() {
+            self.draw_button(d, self.btn_rect(vp, b), label, self.focus == b, self.enabled(b));
+        }
+
+        // Ayuda según los controles activos
+        let hint = bindings.help_text(CHOICE_HELP);
+        let hf = 18;
+        let hw = d.measure_text(&hint, hf);
+        let (_, by, _, bh) = self.btn_rect(vp, BTN_RETRY);
+        d.draw_text(&hint, (sw - hw)/2, by + bh + 14, hf, Color::new(230,230,230,220));
+    }
+
+    fn draw_button(&self, d: &mut RaylibDrawHandle, rect: (i32,i32,i32,i32), label: &str, focused: bool, enabled: bool) {
+        let (x, y, w, h) = rect;
+        let round = 0.5; let segs = 10;
+        let bg = if !enabled {
+            Color::new(70,50,50,255)
+        } else if focused {
+            Color::new(190,70,70,255)
+        } else {
+            Color::new(140,45,45,255)
+        };
+        let fg = if enabled { Color::WHITE } else { Color::new(150,130,130,255) };
+        d.draw_rectangle_rounded(Rectangle{ x: x as f32, y: y as f32, width: w as f32, height: h as f32 }, round, segs, bg);
+        d.draw_rectangle_rounded_lines(Rectangle{ x: x as f32, y: y as f32, width: w as f32, height: h as f32 }, round, segs, fg);
+
+        let fs = 24;
+        let lw = d.measure_text(label, fs);
+        d.draw_text(label, x + (w - lw)/2, y + (h - fs)/2, fs, fg);
+    }
+}
@@ -1,7 +1,7 @@
 use raylib::prelude::*;
-use crate::consts::{SCREEN_W, SCREEN_H};
 use crate::map::Map;
-use crate::types::{SlotItem, Item, ConsumableType, Rarity, Weapon};
+use crate::types::{SlotItem, Item, ConsumableType, Rarity, Weapon, Viewport};
+use crate::input::{Action, Bindings};
 
 pub struct Minimap {
     /// ¿está expandido?
@@ -28,28 +28,26 @@ impl Default for Minimap {
 impl Minimap {
     pub fn new() -> Self { Self::default() }
 
-    /// Llama cada frame para manejar la tecla M
-    pub fn handle_input(&mut self, rl: &RaylibHandle) {
-        if rl.is_key_pressed(KeyboardKey::KEY_M) {
+    /// Llama cada frame para manejar la tecla del minimapa (M por defecto)
+    pub fn handle_input(&mut self, rl: &RaylibHandle, input: &Bindings) {
+        if input.pressed(rl, Action::ToggleMap) {
             self.expanded = !self.expanded;
         }
     }
 
-    /// Devuelve (x, y, w, h) del rectángulo del minimapa según su estado actual.
-    pub fn bounds(&self) -> (i32, i32, i32, i32) {
+    /// Devuelve (x, y, w, h) del rectángulo del minimapa según su estado actual,
+    /// anclado a la esquina superior derecha de la ventana.
+    pub fn bounds(&self, vp: Viewport) -> (i32, i32, i32, i32) {
         let (mw, mh) = if self.expanded { self.large_size } else { self.small_size };
-        let x0 = SCREEN_W - self.margin - mw;
+        let x0 = vp.w - self.margin - mw;
         let y0 = self.margin;
         (x0, y0, mw, mh)
     }
 
     /// Dibuja el minimapa (paredes + jugador con flecha de orientación)
-    pub fn draw(&self, d: &mut RaylibDrawHandle, map: &Map, player_x: f32, player_y: f32, player_angle: f32) {
-        // tamaño actual según estado
-        let (mw, mh) = if self.expanded { self.large_size } else { self.small_size };
-
-        let x0 = SCREEN_W - self.margin - mw;
-        let y0 = self.margin;
+    pub fn draw(&self, d: &mut RaylibDrawHandle, vp: Viewport, map: &Map, player_x: f32, player_y: f32, player_angle: f32) {
+        // rectángulo actual según estado
+        let (x0, y0, mw, mh) = self.bounds(vp);
 
         // fondo semitransparente + marco
         d.draw_rectangle(x0 - 2, y0 - 2, mw + 4, mh + 4, Color::GRAY);
@@ -161,16 +159,18 @@ fn draw_icon_and_text(
     circle_d + gap + tw
 }
 
-/// Dibuja, bajo el minimapa, el tiempo, enemigos vivos y kills.
+/// Dibuja, bajo el minimapa, el tiempo, enemigos vivos, kills y kills de un tiro a la cabeza.
 /// Se alinea al borde derecho del minimapa y **garantiza** que todo cabe sin salirse.
 pub fn draw_top_right_stats(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     minimap: &Minimap,
     elapsed_secs: f32,
     enemies_left: usize,
     kills: u32,
+    headshots: u32,
 ) {
-    let (mx, my, mw, mh) = minimap.bounds();
+    let (mx, my, mw, mh) = minimap.bounds(vp);
 
     // fila debajo del minimapa
     let margin = 8;
@@ -181,6 +181,7 @@ pub fn draw_top_right_stats(
     let t_text = format_time(elapsed_secs);
     let e_text = format!("{}", enemies_left);
     let k_text = format!("{}", kills);
+    let h_text = format!("{}", headshots);
 
     // medir anchos (sin dibujar) para posicionar de derecha a izquierda
     let gap_badges = 12;
@@ -193,16 +194,17 @@ pub fn draw_top_right_stats(
     let w_time  = circle_d + icon_text_gap + d.measure_text(&t_text, font_size);
     let w_alive = circle_d + icon_text_gap + d.measure_text(&e_text, font_size);
     let w_kills = circle_d + icon_text_gap + d.measure_text(&k_text, font_size);
+    let w_heads = circle_d + icon_text_gap + d.measure_text(&h_text, font_size);
 
     // ancho total de la fila
-    let total_w = w_time + w_alive + w_kills + 2 * gap_badges;
+    let total_w = w_time + w_alive + w_kills + w_heads + 3 * gap_badges;
 
     // alineamos al borde derecho del minimapa y nos aseguramos de no salirnos
     let right = mx + mw;
     let mut start_x = right - total_w;
     if start_x < mx { start_x = mx; }                    // no sobrepasar el borde izquierdo del minimapa
-    if start_x + total_w > SCREEN_W - margin {           // clamp al borde derecho de pantalla
-        start_x = (SCREEN_W - margin - total_w).max(mx); // prioriza que quede bajo el minimapa
+    if start_x + total_w > vp.w - margin {               // clamp al borde derecho de pantalla
+        start_x = (vp.w - margin - total_w).max(mx); // prioriza que quede bajo el minimapa
     }
 
     // dibujar izquierda → derecha
@@ -211,7 +213,9 @@ pub fn draw_top_right_stats(
     x += gap_badges;
     x += draw_icon_and_text(d, x, y, "E", &e_text, font_size);
     x += gap_badges;
-    let _ = draw_icon_and_text(d, x, y, "K", &k_text, font_size);
+    x += draw_icon_and_text(d, x, y, "K", &k_text, font_size);
+    x += gap_badges;
+    let _ = draw_icon_and_text(d, x, y, "H", &h_text, font_size);
 }
 
 
@@ -299,26 +303,30 @@ fn consumable_bg(c: ConsumableType) -> Color {
     match c {
         ConsumableType::HealthSmall | ConsumableType::HealthBig => Color::new(120,210,120,220),
         ConsumableType::ShieldSmall | ConsumableType::ShieldBig => Color::new(120,180,230,220),
+        ConsumableType::Grenade => Color::new(110,140,80,220),
     }
 }
 fn slot_short_text(item: &Item) -> (&'static str, Color) {
     match item {
-        Item::Weapon(w, _) => {
-            let t = match w.wtype {
-                crate::types::WeaponType::Pistol         => "PST",
-                crate::types::WeaponType::SMG            => "SMG",
-                crate::types::WeaponType::Rifle          => "RFL",
-                crate::types::WeaponType::Shotgun        => "ESC",
-                crate::types::WeaponType::RocketLauncher => "RKT",
+        Item::Melee(m) => {
+            let t = match m {
+                MeleeType::Fist  => "PUÑ",
+                MeleeType::Knife => "CUC",
+                MeleeType::Bat   => "BAT",
             };
             (t, Color::WHITE)
         }
+        Item::Weapon(w, _) => {
+            let t = w.short;
+            (t, Color::WHITE)
+        }
         Item::Consumable(c) => {
             let t = match c {
                 ConsumableType::HealthSmall => "HP+20",
                 ConsumableType::HealthBig   => "HP+100",
                 ConsumableType::ShieldSmall => "SH+25",
                 ConsumableType::ShieldBig   => "SH+50",
+                ConsumableType::Grenade     => "GRN",
             };
             (t, Color::WHITE)
         }
@@ -383,14 +391,20 @@ pub fn cooldown_colors_for_health() -> (Color, Color) {
 pub fn cooldown_colors_for_shield() -> (Color, Color) {
     (Color::new(61, 153, 255, 255), Color::new(10, 25, 40, 210)) // azul + fondo azulado oscuro
 }
+pub fn cooldown_colors_for_grenade() -> (Color, Color) {
+    (Color::new(230, 160, 50, 255), Color::new(40, 28, 10, 210)) // naranja + fondo tostado oscuro
+}
 
 
 // ===================== HUD: Contador de munición (centro-abajo) =====================
 
 pub fn draw_ammo_center_bottom(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     mag: i32,      // balas en el cargador
     reserve: i32,  // balas en reserva
+    mode: &str,    // modo de disparo (SEMI / RÁFAGA / AUTO)
+    can_toggle: bool, // el arma tiene más de un modo: la etiqueta sale resaltada
 ) {
     let margin_bottom = 28;
     let fs_left  = 44; // tamaño número izquierdo (mag)
@@ -412,12 +426,18 @@ pub fn draw_ammo_center_bottom(
     let bullet_gap = 4;
     let bullets_total_w = bullet_w * 3 + bullet_gap * 2;
 
+    // etiqueta del modo de disparo (a la derecha del icono)
+    let fs_mode = 16;
+    let gap_mode = 14;
+    let mode_pad = 6;
+    let mode_w = d.measure_text(mode, fs_mode) + mode_pad * 2;
+
     // ancho total
-    let total_w = left_w + gap_nums + right_w + gap_icon + bullets_total_w;
+    let total_w = left_w + gap_nums + right_w + gap_icon + bullets_total_w + gap_mode + mode_w;
 
     // posición base (centrado horizontal)
-    let x0 = (crate::consts::SCREEN_W - total_w) / 2;
-    let y_base = crate::consts::SCREEN_H - margin_bottom - fs_left; // línea base visual
+    let x0 = (vp.w - total_w) / 2;
+    let y_base = vp.h - margin_bottom - fs_left; // línea base visual
 
     // ---- dibujar: número izquierdo (mag) con sombra suave ----
 // número izquierdo (mag)
@@ -453,6 +473,18 @@ for _ in 0..3 {
     bx += bullet_w + bullet_gap;
 }
 
+// modo de disparo en una cajita; si se puede cambiar, con borde blanco
+let xm = xi + bullets_total_w + gap_mode;
+let mh = fs_mode + 8;
+let my = cy - mh / 2;
+let rect = Rectangle { x: xm as f32, y: my as f32, width: mode_w as f32, height: mh as f32 };
+d.draw_rectangle_rounded(rect, 0.4, 6, Color::new(0, 0, 0, 140));
+let fg = if can_toggle { Color::WHITE } else { Color::new(200, 200, 200, 200) };
+if can_toggle {
+    d.draw_rectangle_rounded_lines(rect, 0.4, 6, fg);
+}
+d.draw_text(mode, xm + mode_pad, my + 4, fs_mode, fg);
+
 }
 
 
@@ -460,6 +492,7 @@ for _ in 0..3 {
 // ====== BARRAS VIDA/ESCUDO: ahora ABAJO-IZQUIERDA (mantenemos el nombre) ======
 pub fn draw_bottom_right_health_shield(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     hp: i32,
     shield: i32,
     max_hp: i32,
@@ -473,7 +506,7 @@ pub fn draw_bottom_right_health_shield(
 
     // ⬇️ posición: esquina inferior-izquierda (cambiado)
     let x = margin;
-    let y_bottom = SCREEN_H - margin - bar_h;       // VIDA abajo
+    let y_bottom = vp.h - margin - bar_h;           // VIDA abajo
     let y_top    = y_bottom - gap - bar_h;          // ESCUDO arriba
 
     // Colores
@@ -490,9 +523,67 @@ pub fn draw_bottom_right_health_shield(
 
 
 
+/// Barra fina de estamina encima de ESCUDO/VIDA (sólo si no está llena)
+pub fn draw_stamina_bar_bottom_left(
+    d: &mut RaylibDrawHandle,
+    vp: Viewport,
+    stamina: f32,
+    max_stamina: f32,
+) {
+    if max_stamina <= 0.0 || stamina >= max_stamina { return; }
+
+    // Misma geometría que draw_bottom_right_health_shield
+    let total_w = 420;
+    let bar_h   = 26;
+    let gap     = 10;
+    let margin  = 18;
+    let stamina_h = 8;
+    let y_shield = vp.h - margin - bar_h - gap - bar_h;
+    let y = y_shield - gap - stamina_h;
+
+    let pct = (stamina / max_stamina).clamp(0.0, 1.0);
+    d.draw_rectangle_rounded(
+        Rectangle { x: margin as f32, y: y as f32, width: total_w as f32, height: stamina_h as f32 },
+        0.5, 6, Color::new(90, 80, 30, 170)
+    );
+    d.draw_rectangle_rounded(
+        Rectangle { x: margin as f32, y: y as f32, width: total_w as f32 * pct, height: stamina_h as f32 },
+        0.5, 6, Color::new(240, 210, 70, 255)
+    );
+}
+
+/// Vidas (reapariciones en checkpoint) como corazones, encima de estamina/ESCUDO/VIDA.
+/// Las gastadas salen apagadas.
+pub fn draw_lives_bottom_left(d: &mut RaylibDrawHandle, vp: Viewport, lives: u32, max_lives: u32) {
+    // Misma geometría que draw_bottom_right_health_shield (+ hueco de la estamina)
+    let bar_h   = 26;
+    let gap     = 10;
+    let margin  = 18;
+    let stamina_h = 8;
+    let size = 18; // alto de cada corazón
+    let y_shield = vp.h - margin - bar_h - gap - bar_h;
+    let y = y_shield - gap - stamina_h - gap - size;
+
+    for i in 0..max_lives {
+        let x = margin + i as i32 * (size + 8);
+        let col = if i < lives { Color::new(230, 50, 70, 255) } else { Color::new(90, 60, 60, 160) };
+        // dos lóbulos + punta
+        let r = size as f32 / 4.0;
+        d.draw_circle(x + r as i32, y + r as i32, r, col);
+        d.draw_circle(x + (3.0 * r) as i32, y + r as i32, r, col);
+        d.draw_triangle(
+            Vector2::new(x as f32, y as f32 + r),
+            Vector2::new(x as f32 + size as f32 / 2.0, (y + size) as f32),
+            Vector2::new(x as f32 + size as f32, y as f32 + r),
+            col,
+        );
+    }
+}
+
 // ====== SLOTS: ahora ABAJO-DERECHA (con ajuste hacia arriba para que se vean las teclas) ======
 pub fn draw_slots_bar_bottom_left(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     slots: &[Option<SlotItem>; 5],
     selected: Option<usize>,
 ) {
@@ -508,11 +599,11 @@ pub fn draw_slots_bar_bottom_left(
 
     // ⬇️ posición: esquina inferior-derecha
     let row_w: i32 = (count as i32) * tile + ((count as i32) - 1) * gap;
-    let base_x: i32 = SCREEN_W - margin - row_w;
+    let base_x: i32 = vp.w - margin - row_w;
 
     // Subimos la fila para que la pastilla no se corte:
-    // y + tile + label_gap + pill_h ≤ SCREEN_H - margin
-    let base_y: i32 = SCREEN_H - margin - tile - (label_gap + pill_h);
+    // y + tile + label_gap + pill_h ≤ vp.h - margin
+    let base_y: i32 = vp.h - margin - tile - (label_gap + pill_h);
 
     for i in 0..count {
         let x = base_x + (i as i32) * (tile + gap);
@@ -525,6 +616,7 @@ pub fn draw_slots_bar_bottom_left(
                 match &s.item {
                     Item::Weapon(w, _) => { bg = rarity_bg(w.rarity); }
                     Item::Consumable(c) => { bg = consumable_bg(*c); }
+                    Item::Melee(_) => { bg = Color::new(150, 110, 80, 220); }
                 }
             }
         } else {
@@ -559,6 +651,7 @@ pub fn draw_slots_bar_bottom_left(
                 let (sub, sub_col) = match &s.item {
                     Item::Weapon(w, ws) => (format!("{}/{}", ws.ammo_in_mag, w.mag_size), Color::new(240,240,240,255)),
                     Item::Consumable(_) => (format!("x{}", s.count), Color::new(240,240,240,255)),
+                    Item::Melee(_) => (String::new(), Color::new(240,240,240,255)), // sin munición
                 };
                 let tw2 = d.measure_text(&sub, fs2);
                 d.draw_text(&sub, x + (tile - tw2) / 2, y + tile/2 + 2, fs2, sub_col);
@@ -594,10 +687,36 @@ pub fn draw_slots_bar_bottom_left(
     }
 }
 
+/// Barra de carga del lanzamiento de granada (0..1), centrada abajo en el mismo
+/// hueco que el círculo de cooldown de los consumibles.
+pub fn draw_grenade_charge_center_bottom(d: &mut RaylibDrawHandle, vp: Viewport, charge: f32) {
+    if charge <= 0.0 { return; }
+
+    // Misma línea base que draw_ammo_center_bottom
+    let ammo_margin_bottom: i32 = 28;
+    let ammo_fs_left: i32 = 44;
+    let y_base = vp.h - ammo_margin_bottom - ammo_fs_left;
+
+    let (w, h) = (220, 14);
+    let x = (vp.w - w) / 2;
+    let y = y_base - h - 30;
+    let (fill, bg) = cooldown_colors_for_grenade();
+
+    d.draw_rectangle(x, y, w, h, bg);
+    d.draw_rectangle(x, y, (w as f32 * charge.clamp(0.0, 1.0)) as i32, h, fill);
+    d.draw_rectangle_lines(x, y, w, h, Color::new(230, 230, 230, 200));
+
+    let label = "Lanzar";
+    let fs = 16;
+    let tw = d.measure_text(label, fs);
+    d.draw_text(label, (vp.w - tw) / 2, y - fs - 4, fs, Color::WHITE);
+}
+
 /// Círculo de cooldown de consumible (vida/escudo) centrado abajo,
 /// colocado justo ENCIMA de los números de balas para no sobreponerse.
 pub fn draw_consumable_cooldown_center_bottom(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     remaining: f32,           // segundos restantes (slot.cd)
     total: f32,               // segundos totales del consumo
     ctype: ConsumableType,    // para elegir colores (vida/escudo)
@@ -605,21 +724,22 @@ pub fn draw_consumable_cooldown_center_bottom(
     if total <= 0.0 || remaining <= 0.0 { return; }
 
     // Misma “línea base” que usamos en draw_ammo_center_bottom:
-    // y_base = SCREEN_H - margin_bottom - fs_left
+    // y_base = vp.h - margin_bottom - fs_left
     let ammo_margin_bottom: i32 = 28;
     let ammo_fs_left: i32 = 44;
-    let y_base = SCREEN_H - ammo_margin_bottom - ammo_fs_left;
+    let y_base = vp.h - ammo_margin_bottom - ammo_fs_left;
 
     // Colocamos el círculo centrado, un poco por encima de la línea del ammo HUD
     let radius: i32 = 36;
     let gap_above_ammo: i32 = 14;
-    let cx = SCREEN_W / 2;
+    let cx = vp.w / 2;
     let cy = y_base - radius - gap_above_ammo;
 
     // Colores según tipo de consumible (vida/escudo)
     let (arc, bg) = match ctype {
         ConsumableType::HealthSmall | ConsumableType::HealthBig => cooldown_colors_for_health(),
         ConsumableType::ShieldSmall | ConsumableType::ShieldBig => cooldown_colors_for_shield(),
+        ConsumableType::Grenade => cooldown_colors_for_grenade(),
     };
 
     // Dibuja el círculo con texto de segundos (ya lo tenías implementado)
@@ -627,45 +747,60 @@ pub fn draw_consumable_cooldown_center_bottom(
 }
 
 
-use crate::types::WeaponType;
+use crate::types::{WeaponType, MeleeType};
 use raylib::prelude::Texture2D;
+/// Texturas del arma empuñada, una por arma del catálogo (índice = `WeaponType`) + las de cuerpo a cuerpo
 pub struct WeaponHudTextures {
-    pub pistol:   Texture2D,
-    pub smg:      Texture2D,
-    pub rifle:    Texture2D,
-    pub shotgun:  Texture2D,
-    pub rocket:   Texture2D,
+    pub held: Vec<Texture2D>,
+    pub knife: Texture2D,
+    pub bat:   Texture2D,
 }
 
 impl WeaponHudTextures {
-    pub fn tex(&self, wt: WeaponType) -> &Texture2D {
-        match wt {
-            WeaponType::Pistol         => &self.pistol,
-            WeaponType::SMG            => &self.smg,
-            WeaponType::Rifle          => &self.rifle,
-            WeaponType::Shotgun        => &self.shotgun,
-            WeaponType::RocketLauncher => &self.rocket,
+    pub fn tex(&self, wt: WeaponType) -> Option<&Texture2D> {
+        self.held.get(wt.0)
+    }
+    /// El puño no tiene textura (mano vacía)
+    pub fn melee_tex(&self, m: MeleeType) -> Option<&Texture2D> {
+        match m {
+            MeleeType::Fist  => None,
+            MeleeType::Knife => Some(&self.knife),
+            MeleeType::Bat   => Some(&self.bat),
         }
     }
 }
 
 
-/// Dibuja la textura del arma empuñada (solo “idle”), centrada abajo.
+/// Estado de animación del arma empuñada (sale de `Player` y `WeaponState`)
+pub struct HeldWeaponAnim {
+    pub bob_t: f32,           // fase del balanceo
+    pub bob_amp: f32,         // intensidad del balanceo (0..1+)
+    pub kick: f32,            // retroceso (0..1)
+    pub reload: Option<f32>,  // progreso de recarga (0..1) si está recargando
+    pub draw: f32,            // fracción restante al sacar el arma (1 = oculta, 0 = lista)
+    pub spin: f32,            // giro del cañón (0..1): el arma vibra mientras gira
+    pub swing: f32,           // fracción restante del golpe cuerpo a cuerpo (1 = empieza, 0 = nada)
+}
+
+/// Dibuja la textura del arma empuñada, centrada abajo y animada
+/// (balanceo al caminar, retroceso, bajada al recargar, subida al sacarla y barrido al golpear).
 /// Se coloca **por encima** del HUD de balas para que no se encimen.
 pub fn draw_held_weapon_center_bottom(
     d: &mut RaylibDrawHandle,
+    vp: Viewport,
     tex: &Texture2D,
+    anim: &HeldWeaponAnim,
 ) {
     // Reservas del HUD de balas (idénticas a draw_ammo_center_bottom)
     let ammo_margin_bottom: i32 = 28;
     let ammo_fs_left: i32 = 44;
-    let y_base_ammo = SCREEN_H - ammo_margin_bottom - ammo_fs_left;
+    let y_base_ammo = vp.h - ammo_margin_bottom - ammo_fs_left;
 
     // Queremos que la imagen “descanse” un poco por encima del ammo HUD
     let gap = 12;
 
     // Limites máximos para no comer mucha pantalla
-    let max_w = (SCREEN_W as f32 * 0.55) as i32;  // 55% del ancho
+    let max_w = (vp.w as f32 * 0.55) as i32;      // 55% del ancho
     let max_h = 220i32;                           // alto máximo aproximado
 
     let src_w = tex.width();
@@ -680,27 +815,77 @@ pub fn draw_held_weapon_center_bottom(
     let dst_w = (src_w as f32 * scale) as i32;
     let dst_h = (src_h as f32 * scale) as i32;
 
-    let x = (SCREEN_W - dst_w) / 2;
+    let x = (vp.w - dst_w) / 2;
     let y = y_base_ammo - gap - dst_h;
 
+    // Desplazamientos de la animación (proporcionales al tamaño dibujado)
+    let (w_f, h_f) = (dst_w as f32, dst_h as f32);
+    let shake = anim.spin * (anim.spin * 90.0 + anim.bob_t * 7.0).sin() * w_f * 0.006;
+    let bob_x = anim.bob_t.sin() * w_f * 0.04 * anim.bob_amp + shake;
+    let bob_y = (anim.bob_t * 2.0).sin().abs() * h_f * 0.05 * anim.bob_amp;
+    let kick_y = anim.kick * h_f * 0.12;
+    let dip = anim.reload.map(|p| (p.clamp(0.0, 1.0) * std::f32::consts::PI).sin()).unwrap_or(0.0);
+    let dip_y = dip * h_f * 0.45;
+    let draw_y = anim.draw.clamp(0.0, 1.0) * h_f;
+    // golpe: barre de derecha a izquierda y empuja hacia delante (arriba)
+    let sw = anim.swing.clamp(0.0, 1.0);
+    let lunge = (sw * std::f32::consts::PI).sin();
+    let swing_x = (sw - 0.5) * w_f * 0.5 * lunge;
+    let swing_y = -lunge * h_f * 0.12;
+    // giro (grados): el retroceso levanta la punta, la recarga ladea el arma, el golpe la tumba
+    let rot = -anim.kick * 6.0 + dip * 15.0 - lunge * 35.0;
+
+    // Pivote abajo al centro, así el giro parece venir de la mano
+    let px = x as f32 + w_f / 2.0 + bob_x + swing_x;
+    let py = y as f32 + h_f + bob_y + kick_y + dip_y + draw_y + swing_y;
+    let src = Rectangle { x: 0.0, y: 0.0, width: src_w as f32, height: src_h as f32 };
+    let origin = Vector2::new(w_f / 2.0, h_f);
+
     // Sombra muy suave (opcional)
     let shadow_off = 2.0;
     d.draw_texture_pro(
         tex,
-        Rectangle { x: 0.0, y: 0.0, width: src_w as f32, height: src_h as f32 },
-        Rectangle { x: x as f32 + shadow_off, y: y as f32 + shadow_off, width: dst_w as f32, height: dst_h as f32 },
-        Vector2::new(0.0, 0.0),
-        0.0,
+        src,
+        Rectangle { x: px + shadow_off, y: py + shadow_off, width: w_f, height: h_f },
+        origin,
+        rot,
         Color::new(0, 0, 0, 90),
     );
 
     // Imagen principal
     d.draw_texture_pro(
         tex,
-        Rectangle { x: 0.0, y: 0.0, width: src_w as f32, height: src_h as f32 },
-        Rectangle { x: x as f32, y: y as f32, width: dst_w as f32, height: dst_h as f32 },
-        Vector2::new(0.0, 0.0),
-        0.0,
+        src,
+        Rectangle { x: px, y: py, width: w_f, height: h_f },
+        origin,
+        rot,
         Color::WHITE,
     );
 }
+
+
+// ===================== HUD: Mira (centro de la pantalla) =====================
+
+/// Cruz en el centro: sus cuatro brazos se separan `spread_px` (radio del cono de
+/// disparo ya pasado a píxeles de ventana), así se ve cuánto se abre al disparar o moverse.
+pub fn draw_crosshair(d: &mut RaylibDrawHandle, vp: Viewport, spread_px: f32) {
+    let (cx, cy) = (vp.w / 2, vp.h / 2);
+    let gap = 4 + spread_px.round() as i32;
+    let len = 8;
+    let thick = 2;
+    let col = Color::new(240, 240, 240, 220);
+    let shadow = Color::new(0, 0, 0, 140);
+
+    // (x, y, w, h) de cada brazo: izquierda, derecha, arriba, abajo
+    let arms = [
+        (cx - gap - len, cy - thick / 2, len, thick),
+        (cx + gap,       cy - thick / 2, len, thick),
+        (cx - thick / 2, cy - gap - len, thick, len),
+        (cx - thick / 2, cy + gap,       thick, len),
+    ];
+    for (x, y, w, h) in arms {
+        d.draw_rectangle(x - 1, y - 1, w + 2, h + 2, shadow);
+        d.draw_rectangle(x, y, w, h, col);
+    }
+    d.draw_rectangle(cx - 1, cy - 1, 2, 2, col);
+}
@@ -0,0 +1,562 @@
+use std::fs;
+use std::path::Path;
+use raylib::prelude::*;
+use raylib::consts::{KeyboardKey, MouseButton, GamepadButton, GamepadAxis};
+use crate::consts::{PAD_INDEX, PAD_DEADZONE};
+
+/// Acciones del juego. El código de juego pregunta por acciones,
+/// nunca por teclas: qué tecla/botón/stick dispara cada una lo decide `Bindings`.
+/// Las `Menu*` (navegación de menús) y `Quit` sólo se cambian editando el archivo.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    MoveForward,
+    MoveBack,
+    StrafeLeft,
+    StrafeRight,
+    TurnLeft,
+    TurnRight,
+    LookUp,
+    LookDown,
+    Jump,
+    Crouch,
+    Sprint,
+    Fire,
+    Reload,
+    FireMode,
+    Melee,
+    Interact,
+    UseConsumable,
+    Slot1,
+    Slot2,
+    Slot3,
+    Slot4,
+    Slot5,
+    Unarmed,
+    NextSlot,
+    PrevSlot,
+    ToggleMap,
+    Fullscreen,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    MenuConfirm,
+    MenuBack,
+    MenuClear,
+    MenuControls,
+    Quit,
+}
+
+// Las primeras `GAME_ACTIONS` de `Action::ALL` son las del juego (reasignables en el menú)
+const GAME_ACTIONS: usize = 27;
+
+impl Action {
+    /// Todas: primero las del juego, en el orden de la pantalla de controles
+    pub const ALL: [Action; 36] = [
+        Action::MoveForward, Action::MoveBack, Action::StrafeLeft, Action::StrafeRight,
+        Action::TurnLeft, Action::TurnRight, Action::LookUp, Action::LookDown,
+        Action::Jump, Action::Crouch, Action::Sprint,
+        Action::Fire, Action::Reload, Action::FireMode, Action::Melee, Action::Interact, Action::UseConsumable,
+        Action::Slot1, Action::Slot2, Action::Slot3, Action::Slot4, Action::Slot5, Action::Unarmed,
+        Action::NextSlot, Action::PrevSlot,
+        Action::ToggleMap, Action::Fullscreen,
+        Action::MenuUp, Action::MenuDown, Action::MenuLeft, Action::MenuRight,
+        Action::MenuConfirm, Action::MenuBack, Action::MenuClear, Action::MenuControls,
+        Action::Quit,
+    ];
+
+    /// Las que aparecen en la pantalla de controles
+    pub fn game() -> &'static [Action] {
+        &Action::ALL[..GAME_ACTIONS]
+    }
+
+    /// Nombre en el archivo de controles
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveForward   => "move_forward",
+            Action::MoveBack      => "move_back",
+            Action::StrafeLeft    => "strafe_left",
+            Action::StrafeRight   => "strafe_right",
+            Action::TurnLeft      => "turn_left",
+            Action::TurnRight     => "turn_right",
+            Action::LookUp        => "look_up",
+            Action::LookDown      => "look_down",
+            Action::Jump          => "jump",
+            Action::Crouch        => "crouch",
+            Action::Sprint        => "sprint",
+            Action::Fire          => "fire",
+            Action::Reload        => "reload",
+            Action::FireMode      => "fire_mode",
+            Action::Melee         => "melee",
+            Action::Interact      => "interact",
+            Action::UseConsumable => "use_consumable",
+            Action::Slot1         => "slot_1",
+            Action::Slot2         => "slot_2",
+            Action::Slot3         => "slot_3",
+            Action::Slot4         => "slot_4",
+            Action::Slot5         => "slot_5",
+            Action::Unarmed       => "unarmed",
+            Action::NextSlot      => "next_slot",
+            Action::PrevSlot      => "prev_slot",
+            Action::ToggleMap     => "toggle_map",
+            Action::Fullscreen    => "fullscreen",
+            Action::MenuUp        => "menu_up",
+            Action::MenuDown      => "menu_down",
+            Action::MenuLeft      => "menu_left",
+            Action::MenuRight     => "menu_right",
+            Action::MenuConfirm   => "menu_confirm",
+            Action::MenuBack      => "menu_back",
+            Action::MenuClear     => "menu_clear",
+            Action::MenuControls  => "menu_controls",
+            Action::Quit          => "quit",
+        }
+    }
+
+    /// Texto para la pantalla de controles
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveForward   => "Avanzar",
+            Action::MoveBack      => "Retroceder",
+            Action::StrafeLeft    => "Lateral izquierda",
+            Action::StrafeRight   => "Lateral derecha",
+            Action::TurnLeft      => "Girar izquierda",
+            Action::TurnRight     => "Girar derecha",
+            Action::LookUp        => "Mirar arriba",
+            Action::LookDown      => "Mirar abajo",
+            Action::Jump          => "Saltar",
+            Action::Crouch        => "Agacharse",
+            Action::Sprint        => "Correr",
+            Action::Fire          => "Disparar / golpear",
+            Action::Reload        => "Recargar",
+            Action::FireMode      => "Modo de disparo",
+            Action::Melee         => "Golpe rápido",
+            Action::Interact      => "Interactuar",
+            Action::UseConsumable => "Usar consumible",
+            Action::Slot1         => "Slot 1",
+            Action::Slot2         => "Slot 2",
+            Action::Slot3         => "Slot 3",
+            Action::Slot4         => "Slot 4",
+            Action::Slot5         => "Slot 5",
+            Action::Unarmed       => "Mano vacía",
+            Action::NextSlot      => "Slot siguiente",
+            Action::PrevSlot      => "Slot anterior",
+            Action::ToggleMap     => "Minimapa",
+            Action::Fullscreen    => "Pantalla completa",
+            Action::MenuUp        => "Menú: arriba",
+            Action::MenuDown      => "Menú: abajo",
+            Action::MenuLeft      => "Menú: izquierda",
+            Action::MenuRight     => "Menú: derecha",
+            Action::MenuConfirm   => "Menú: aceptar",
+            Action::MenuBack      => "Menú: volver",
+            Action::MenuClear     => "Menú: de fábrica",
+            Action::MenuControls  => "Menú: controles",
+            Action::Quit          => "Salir del juego",
+        }
+    }
+
+    fn from_name(s: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|a| a.name() == s)
+    }
+
+    /// Controles de fábrica (teclado/mouse primero, luego mando)
+    fn default_bindings(self) -> &'static [Binding] {
+        use KeyboardKey::*;
+        use GamepadButton::*;
+        use GamepadAxis::*;
+        use Binding::{Key, Pad, Stick};
+        match self {
+            Action::MoveForward   => &[Key(KEY_W), Stick(GAMEPAD_AXIS_LEFT_Y, false)],
+            Action::MoveBack      => &[Key(KEY_S), Stick(GAMEPAD_AXIS_LEFT_Y, true)],
+            Action::StrafeLeft    => &[Key(KEY_A), Stick(GAMEPAD_AXIS_LEFT_X, false)],
+            Action::StrafeRight   => &[Key(KEY_D), Stick(GAMEPAD_AXIS_LEFT_X, true)],
+            Action::TurnLeft      => &[Key(KEY_LEFT), Stick(GAMEPAD_AXIS_RIGHT_X, false)],
+            Action::TurnRight     => &[Key(KEY_RIGHT), Stick(GAMEPAD_AXIS_RIGHT_X, true)],
+            Action::LookUp        => &[Key(KEY_UP), Stick(GAMEPAD_AXIS_RIGHT_Y, false)],
+            Action::LookDown      => &[Key(KEY_DOWN), Stick(GAMEPAD_AXIS_RIGHT_Y, true)],
+            Action::Jump          => &[Key(KEY_Q), Pad(GAMEPAD_BUTTON_RIGHT_FACE_RIGHT)],
+            Action::Crouch        => &[Key(KEY_C), Pad(GAMEPAD_BUTTON_RIGHT_THUMB)],
+            Action::Sprint        => &[Key(KEY_LEFT_SHIFT), Pad(GAMEPAD_BUTTON_LEFT_THUMB)],
+            Action::Fire          => &[Key(KEY_SPACE), Binding::Mouse(MouseButton::MOUSE_BUTTON_LEFT), Pad(GAMEPAD_BUTTON_RIGHT_TRIGGER_2)],
+            Action::Reload        => &[Key(KEY_R), Pad(GAMEPAD_BUTTON_RIGHT_FACE_LEFT)],
+            Action::FireMode      => &[Key(KEY_B), Pad(GAMEPAD_BUTTON_LEFT_FACE_LEFT)],
+            Action::Melee         => &[Key(KEY_V), Pad(GAMEPAD_BUTTON_LEFT_FACE_RIGHT)],
+            Action::Interact      => &[Key(KEY_E), Pad(GAMEPAD_BUTTON_RIGHT_FACE_DOWN)],
+            Action::UseConsumable => &[Key(KEY_F), Pad(GAMEPAD_BUTTON_RIGHT_FACE_UP)],
+            Action::Slot1         => &[Key(KEY_ONE)],
+            Action::Slot2         => &[Key(KEY_TWO)],
+            Action::Slot3         => &[Key(KEY_THREE)],
+            Action::Slot4         => &[Key(KEY_FOUR)],
+            Action::Slot5         => &[Key(KEY_FIVE)],
+            Action::Unarmed       => &[Key(KEY_ZERO), Pad(GAMEPAD_BUTTON_LEFT_FACE_DOWN)],
+            Action::NextSlot      => &[Pad(GAMEPAD_BUTTON_RIGHT_TRIGGER_1)],
+            Action::PrevSlot      => &[Pad(GAMEPAD_BUTTON_LEFT_TRIGGER_1)],
+            Action::ToggleMap     => &[Key(KEY_M), Pad(GAMEPAD_BUTTON_MIDDLE_LEFT)],
+            Action::Fullscreen    => &[Key(KEY_F11)],
+            Action::MenuUp        => &[Key(KEY_UP), Pad(GAMEPAD_BUTTON_LEFT_FACE_UP), Stick(GAMEPAD_AXIS_LEFT_Y, false)],
+            Action::MenuDown      => &[Key(KEY_DOWN), Pad(GAMEPAD_BUTTON_LEFT_FACE_DOWN), Stick(GAMEPAD_AXIS_LEFT_Y, true)],
+            Action::MenuLeft      => &[Key(KEY_LEFT), Pad(GAMEPAD_BUTTON_LEFT_FACE_LEFT), Stick(GAMEPAD_AXIS_LEFT_X, false)],
+            Action::MenuRight     => &[Key(KEY_RIGHT), Pad(GAMEPAD_BUTTON_LEFT_FACE_RIGHT), Stick(GAMEPAD_AXIS_LEFT_X, true)],
+            Action::MenuConfirm   => &[Key(KEY_ENTER), Key(KEY_SPACE), Pad(GAMEPAD_BUTTON_RIGHT_FACE_DOWN), Pad(GAMEPAD_BUTTON_MIDDLE_RIGHT)],
+            Action::MenuBack      => &[Key(KEY_ESCAPE), Pad(GAMEPAD_BUTTON_RIGHT_FACE_RIGHT)],
+            Action::MenuClear     => &[Key(KEY_BACKSPACE), Pad(GAMEPAD_BUTTON_RIGHT_FACE_UP)],
+            Action::MenuControls  => &[Key(KEY_TAB), Pad(GAMEPAD_BUTTON_MIDDLE_LEFT)],
+            Action::Quit          => &[Key(KEY_ESCAPE)],
+        }
+    }
+}
+
+/// Una tecla, un botón del mouse, un botón del mando o un sentido de un stick
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Binding {
+    Key(KeyboardKey),
+    Mouse(MouseButton),
+    Pad(GamepadButton),
+    Stick(GamepadAxis, bool), // eje del stick y sentido (true = positivo: derecha/abajo)
+}
+
+// (nombre en el archivo, texto en pantalla, tecla). Sólo estas teclas se pueden asignar.
+const KEY_NAMES: &[(&str, &str, KeyboardKey)] = {
+    use KeyboardKey::*;
+    &[
+        ("A", "A", KEY_A), ("B", "B", KEY_B), ("C", "C", KEY_C), ("D", "D", KEY_D),
+        ("E", "E", KEY_E), ("F", "F", KEY_F), ("G", "G", KEY_G), ("H", "H", KEY_H),
+        ("I", "I", KEY_I), ("J", "J", KEY_J), ("K", "K", KEY_K), ("L", "L", KEY_L),
+        ("M", "M", KEY_M), ("N", "N", KEY_N), ("O", "O", KEY_O), ("P", "P", KEY_P),
+        ("Q", "Q", KEY_Q), ("R", "R", KEY_R), ("S", "S", KEY_S), ("T", "T", KEY_T),
+        ("U", "U", KEY_U), ("V", "V", KEY_V), ("W", "W", KEY_W), ("X", "X", KEY_X),
+        ("Y", "Y", KEY_Y), ("Z", "Z", KEY_Z),
+        ("0", "0", KEY_ZERO), ("1", "1", KEY_ONE), ("2", "2", KEY_TWO), ("3", "3", KEY_THREE),
+        ("4", "4", KEY_FOUR), ("5", "5", KEY_FIVE), ("6", "6", KEY_SIX), ("7", "7", KEY_SEVEN),
+        ("8", "8", KEY_EIGHT), ("9", "9", KEY_NINE),
+        ("F1", "F1", KEY_F1), ("F2", "F2", KEY_F2), ("F3", "F3", KEY_F3), ("F4", "F4", KEY_F4),
+        ("F5", "F5", KEY_F5), ("F6", "F6", KEY_F6), ("F7", "F7", KEY_F7), ("F8", "F8", KEY_F8),
+        ("F9", "F9", KEY_F9), ("F10", "F10", KEY_F10), ("F11", "F11", KEY_F11), ("F12", "F12", KEY_F12),
+        ("SPACE", "ESPACIO", KEY_SPACE), ("ENTER", "ENTER", KEY_ENTER), ("TAB", "TAB", KEY_TAB),
+        ("BACKSPACE", "RETROCESO", KEY_BACKSPACE), ("ESCAPE", "ESC", KEY_ESCAPE),
+        ("LEFT_SHIFT", "Shift", KEY_LEFT_SHIFT), ("RIGHT_SHIFT", "Shift der", KEY_RIGHT_SHIFT),
+        ("LEFT_CONTROL", "Ctrl", KEY_LEFT_CONTROL), ("RIGHT_CONTROL", "Ctrl der", KEY_RIGHT_CONTROL),
+        ("LEFT_ALT", "Alt", KEY_LEFT_ALT), ("RIGHT_ALT", "Alt der", KEY_RIGHT_ALT),
+        ("UP", "↑", KEY_UP), ("DOWN", "↓", KEY_DOWN), ("LEFT", "←", KEY_LEFT), ("RIGHT", "→", KEY_RIGHT),
+        ("COMMA", ",", KEY_COMMA), ("PERIOD", ".", KEY_PERIOD), ("MINUS", "-", KEY_MINUS),
+        ("EQUAL", "=", KEY_EQUAL), ("SLASH", "/", KEY_SLASH), ("SEMICOLON", ";", KEY_SEMICOLON),
+        ("APOSTROPHE", "'", KEY_APOSTROPHE), ("GRAVE", "`", KEY_GRAVE),
+        ("LEFT_BRACKET", "[", KEY_LEFT_BRACKET), ("RIGHT_BRACKET", "]", KEY_RIGHT_BRACKET),
+    ]
+};
+
+const MOUSE_NAMES: &[(&str, &str, MouseButton)] = &[
+    ("MOUSE_LEFT",   "Clic izq",   MouseButton::MOUSE_BUTTON_LEFT),
+    ("MOUSE_RIGHT",  "Clic der",   MouseButton::MOUSE_BUTTON_RIGHT),
+    ("MOUSE_MIDDLE", "Clic medio", MouseButton::MOUSE_BUTTON_MIDDLE),
+    ("MOUSE_SIDE",   "Mouse 4",    MouseButton::MOUSE_BUTTON_SIDE),
+    ("MOUSE_EXTRA",  "Mouse 5",    MouseButton::MOUSE_BUTTON_EXTRA),
+];
+
+// Botones del mando con nombres estilo Xbox (A abajo, B derecha, X izquierda, Y arriba)
+const PAD_NAMES: &[(&str, &str, GamepadButton)] = {
+    use GamepadButton::*;
+    &[
+        ("PAD_A", "A", GAMEPAD_BUTTON_RIGHT_FACE_DOWN), ("PAD_B", "B", GAMEPAD_BUTTON_RIGHT_FACE_RIGHT),
+        ("PAD_X", "X", GAMEPAD_BUTTON_RIGHT_FACE_LEFT), ("PAD_Y", "Y", GAMEPAD_BUTTON_RIGHT_FACE_UP),
+        ("PAD_LB", "LB", GAMEPAD_BUTTON_LEFT_TRIGGER_1), ("PAD_RB", "RB", GAMEPAD_BUTTON_RIGHT_TRIGGER_1),
+        ("PAD_LT", "LT", GAMEPAD_BUTTON_LEFT_TRIGGER_2), ("PAD_RT", "RT", GAMEPAD_BUTTON_RIGHT_TRIGGER_2),
+        ("PAD_UP", "Cruceta ↑", GAMEPAD_BUTTON_LEFT_FACE_UP), ("PAD_DOWN", "Cruceta ↓", GAMEPAD_BUTTON_LEFT_FACE_DOWN),
+        ("PAD_LEFT", "Cruceta ←", GAMEPAD_BUTTON_LEFT_FACE_LEFT), ("PAD_RIGHT", "Cruceta →", GAMEPAD_BUTTON_LEFT_FACE_RIGHT),
+        ("PAD_BACK", "Back", GAMEPAD_BUTTON_MIDDLE_LEFT), ("PAD_START", "Start", GAMEPAD_BUTTON_MIDDLE_RIGHT),
+        ("PAD_LS", "LS", GAMEPAD_BUTTON_LEFT_THUMB), ("PAD_RS", "RS", GAMEPAD_BUTTON_RIGHT_THUMB),
+    ]
+};
+
+// Sticks: (nombre, texto, eje, sentido positivo)
+const STICK_NAMES: &[(&str, &str, GamepadAxis, bool)] = {
+    use GamepadAxis::*;
+    &[
+        ("PAD_LX-", "Stick izq ←", GAMEPAD_AXIS_LEFT_X, false), ("PAD_LX+", "Stick izq →", GAMEPAD_AXIS_LEFT_X, true),
+        ("PAD_LY-", "Stick izq ↑", GAMEPAD_AXIS_LEFT_Y, false), ("PAD_LY+", "Stick izq ↓", GAMEPAD_AXIS_LEFT_Y, true),
+        ("PAD_RX-", "Stick der ←", GAMEPAD_AXIS_RIGHT_X, false), ("PAD_RX+", "Stick der →", GAMEPAD_AXIS_RIGHT_X, true),
+        ("PAD_RY-", "Stick der ↑", GAMEPAD_AXIS_RIGHT_Y, false), ("PAD_RY+", "Stick der ↓", GAMEPAD_AXIS_RIGHT_Y, true),
+    ]
+};
+
+impl Binding {
+    /// Sólo las teclas de la tabla tienen nombre (y se pueden asignar)
+    pub fn from_key(key: KeyboardKey) -> Option<Binding> {
+        KEY_NAMES.iter().any(|&(_, _, k)| k == key).then_some(Binding::Key(key))
+    }
+
+    fn parse(s: &str) -> Option<Binding> {
+        let s = s.to_ascii_uppercase();
+        if let Some(&(_, _, k)) = KEY_NAMES.iter().find(|(n, _, _)| *n == s) {
+            return Some(Binding::Key(k));
+        }
+        if let Some(&(_, _, b)) = MOUSE_NAMES.iter().find(|(n, _, _)| *n == s) {
+            return Some(Binding::Mouse(b));
+        }
+        if let Some(&(_, _, b)) = PAD_NAMES.iter().find(|(n, _, _)| *n == s) {
+            return Some(Binding::Pad(b));
+        }
+        STICK_NAMES.iter().find(|(n, _, _, _)| *n == s).map(|&(_, _, ax, pos)| Binding::Stick(ax, pos))
+    }
+
+    /// (nombre en el archivo, texto en pantalla)
+    fn names(self) -> (&'static str, &'static str) {
+        let found = match self {
+            Binding::Key(key) => KEY_NAMES.iter().find(|(_, _, k)| *k == key).map(|e| (e.0, e.1)),
+            Binding::Mouse(btn) => MOUSE_NAMES.iter().find(|(_, _, b)| *b == btn).map(|e| (e.0, e.1)),
+            Binding::Pad(btn) => PAD_NAMES.iter().find(|(_, _, b)| *b == btn).map(|e| (e.0, e.1)),
+            Binding::Stick(ax, pos) => STICK_NAMES.iter().find(|e| e.2 == ax && e.3 == pos).map(|e| (e.0, e.1)),
+        };
+        found.unwrap_or(("?", "?"))
+    }
+
+    fn name(self) -> &'static str { self.names().0 }
+
+    /// Texto corto para HUD y menú
+    pub fn label(self) -> &'static str { self.names().1 }
+
+    /// ¿Es del mando? (al reasignar, teclado/mouse y mando se cambian por separado)
+    pub fn is_pad(self) -> bool {
+        matches!(self, Binding::Pad(_) | Binding::Stick(_, _))
+    }
+
+    /// Primer botón del mouse pulsado este frame (para reasignar)
+    pub fn mouse_pressed(rl: &RaylibHandle) -> Option<Binding> {
+        MOUSE_NAMES.iter()
+            .find(|(_, _, b)| rl.is_mouse_button_pressed(*b))
+            .map(|&(_, _, b)| Binding::Mouse(b))
+    }
+}
+
+/// Zona muerta del stick: por debajo de `PAD_DEADZONE` vale 0 y el resto
+/// se reescala a 0..1 para que no haya salto al salir de ella.
+fn deadzone(v: f32) -> f32 {
+    ((v.abs() - PAD_DEADZONE) / (1.0 - PAD_DEADZONE)).clamp(0.0, 1.0) * v.signum()
+}
+
+// Ejes de los sticks que se leen cada frame (los gatillos van como botones:
+// raylib marca LT/RT como pulsados al pasar de ~10% de recorrido)
+const STICK_AXES: [GamepadAxis; 4] = [
+    GamepadAxis::GAMEPAD_AXIS_LEFT_X, GamepadAxis::GAMEPAD_AXIS_LEFT_Y,
+    GamepadAxis::GAMEPAD_AXIS_RIGHT_X, GamepadAxis::GAMEPAD_AXIS_RIGHT_Y,
+];
+
+/// Acción → teclas/botones/sticks. Cada acción puede tener varias entradas.
+pub struct Bindings {
+    map: Vec<Vec<Binding>>, // índice = `Action as usize`
+    // estado de los sticks (con zona muerta) de este frame y del anterior,
+    // para tratar "empujar el stick" como una pulsación
+    sticks: [f32; 4],
+    sticks_prev: [f32; 4],
+    pad: bool, // ¿hay mando conectado?
+}
+
+impl Bindings {
+    pub fn defaults() -> Self {
+        Self {
+            map: Action::ALL.iter().map(|a| a.default_bindings().to_vec()).collect(),
+            sticks: [0.0; 4],
+            sticks_prev: [0.0; 4],
+            pad: false,
+        }
+    }
+
+    /// Lee los sticks del mando. Llamar una vez por frame, antes de consultar acciones.
+    pub fn update(&mut self, rl: &RaylibHandle) {
+        self.sticks_prev = self.sticks;
+        self.pad = rl.is_gamepad_available(PAD_INDEX);
+        for (i, ax) in STICK_AXES.iter().enumerate() {
+            self.sticks[i] = if self.pad { deadzone(rl.get_gamepad_axis_movement(PAD_INDEX, *ax)) } else { 0.0 };
+        }
+    }
+
+    /// Cuánto empuja el stick en el sentido pedido (0..1)
+    fn stick(&self, ax: GamepadAxis, pos: bool, prev: bool) -> f32 {
+        let Some(i) = STICK_AXES.iter().position(|a| *a == ax) else { return 0.0 };
+        let v = if prev { self.sticks_prev[i] } else { self.sticks[i] };
+        if pos { v.max(0.0) } else { (-v).max(0.0) }
+    }
+
+    /// Valor analógico de una entrada (teclas/botones: 0 o 1)
+    fn binding_value(&self, rl: &RaylibHandle, b: Binding) -> f32 {
+        let on = match b {
+            Binding::Key(k) => rl.is_key_down(k),
+            Binding::Mouse(m) => rl.is_mouse_button_down(m),
+            Binding::Pad(p) => rl.is_gamepad_button_down(PAD_INDEX, p),
+            Binding::Stick(ax, pos) => return self.stick(ax, pos, false),
+        };
+        if on { 1.0 } else { 0.0 }
+    }
+
+    fn binding_pressed(&self, rl: &RaylibHandle, b: Binding) -> bool {
+        match b {
+            Binding::Key(k) => rl.is_key_pressed(k),
+            Binding::Mouse(m) => rl.is_mouse_button_pressed(m),
+            Binding::Pad(p) => rl.is_gamepad_button_pressed(PAD_INDEX, p),
+            // el stick cuenta como pulsado al pasar de la mitad
+            Binding::Stick(ax, pos) => self.stick(ax, pos, false) > 0.5 && self.stick(ax, pos, true) <= 0.5,
+        }
+    }
+
+    /// Primer botón/stick del mando pulsado este frame (para reasignar)
+    pub fn pad_pressed(&self, rl: &RaylibHandle) -> Option<Binding> {
+        if !self.pad { return None; }
+        if let Some(&(_, _, b)) = PAD_NAMES.iter().find(|(_, _, b)| rl.is_gamepad_button_pressed(PAD_INDEX, *b)) {
+            return Some(Binding::Pad(b));
+        }
+        STICK_NAMES.iter()
+            .map(|&(_, _, ax, pos)| Binding::Stick(ax, pos))
+            .find(|b| self.binding_pressed(rl, *b))
+    }
+
+    /// Lee `accion = TECLA, TECLA` (una por línea, `#` comenta).
+    /// Las acciones que no aparecen se quedan con su control de fábrica.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let txt = fs::read_to_string(&path).map_err(|e| format!("No se pudo leer los controles: {e}"))?;
+        let mut out = Self::defaults();
+
+        for (n, line) in txt.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() { continue; }
+
+            let (name, keys) = line.split_once('=')
+                .ok_or_else(|| format!("Controles, línea {}: falta '='", n + 1))?;
+            let action = Action::from_name(name.trim())
+                .ok_or_else(|| format!("Controles, línea {}: acción desconocida '{}'", n + 1, name.trim()))?;
+
+            let mut list = Vec::new();
+            for k in keys.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+                let b = Binding::parse(k)
+                    .ok_or_else(|| format!("Controles, línea {}: tecla desconocida '{k}'", n + 1))?;
+                if !list.contains(&b) { list.push(b); }
+            }
+            out.map[action as usize] = list;
+        }
+        Ok(out)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let mut txt = String::from("# Controles: acción = TECLA[, TECLA…]\n");
+        txt.push_str("# Teclas: A..Z, 0..9, F1..F12, SPACE, ENTER, TAB, LEFT_SHIFT, LEFT_CONTROL, UP, DOWN, LEFT, RIGHT…\n");
+        txt.push_str("# Mouse: MOUSE_LEFT, MOUSE_RIGHT, MOUSE_MIDDLE, MOUSE_SIDE, MOUSE_EXTRA\n");
+        txt.push_str("# Mando: PAD_A, PAD_B, PAD_X, PAD_Y, PAD_LB, PAD_RB, PAD_LT, PAD_RT, PAD_UP…, PAD_BACK, PAD_START, PAD_LS, PAD_RS\n");
+        txt.push_str("# Sticks: PAD_LX-, PAD_LX+, PAD_LY-, PAD_LY+, PAD_RX-, PAD_RX+, PAD_RY-, PAD_RY+\n\n");
+        for a in Action::ALL {
+            let keys: Vec<&str> = self.get(a).iter().map(|b| b.name()).collect();
+            txt.push_str(&format!("{} = {}\n", a.name(), keys.join(", ")));
+        }
+        fs::write(&path, txt).map_err(|e| format!("No se pudo guardar los controles: {e}"))
+    }
+
+    pub fn get(&self, a: Action) -> &[Binding] {
+        &self.map[a as usize]
+    }
+
+    /// Intensidad de la acción, 0..1 (sticks analógicos; teclas 0 o 1)
+    pub fn value(&self, rl: &RaylibHandle, a: Action) -> f32 {
+        self.get(a).iter().map(|b| self.binding_value(rl, *b)).fold(0.0, f32::max)
+    }
+
+    /// Eje -1..1 a partir de dos acciones opuestas (p. ej. StrafeLeft/StrafeRight)
+    pub fn axis(&self, rl: &RaylibHandle, neg: Action, pos: Action) -> f32 {
+        (self.value(rl, pos) - self.value(rl, neg)).clamp(-1.0, 1.0)
+    }
+
+    /// ¿Alguna entrada de la acción mantenida? (stick: más de la mitad)
+    pub fn down(&self, rl: &RaylibHandle, a: Action) -> bool {
+        self.get(a).iter().any(|b| self.binding_value(rl, *b) > 0.5)
+    }
+
+    /// ¿Alguna entrada de la acción pulsada este frame?
+    pub fn pressed(&self, rl: &RaylibHandle, a: Action) -> bool {
+        self.get(a).iter().any(|b| self.binding_pressed(rl, *b))
+    }
+
+    /// Pone `b` en `a` en lugar de sus entradas del mismo tipo (teclado/mouse o mando)
+    /// y se la quita a las demás acciones del juego
+    pub fn rebind(&mut self, a: Action, b: Binding) {
+        for g in Action::game() {
+            self.map[*g as usize].retain(|x| *x != b);
+        }
+        let list = &mut self.map[a as usize];
+        list.retain(|x| x.is_pad() != b.is_pad());
+        list.push(b);
+    }
+
+    /// Vuelve `a` a su control de fábrica (quitándoselo a quien lo tuviera)
+    pub fn reset(&mut self, a: Action) {
+        let defaults = a.default_bindings();
+        for g in Action::game() {
+            self.map[*g as usize].retain(|x| !defaults.contains(x));
+        }
+        self.map[a as usize] = defaults.to_vec();
+    }
+
+    /// "ESPACIO/Clic izq/RT", o "---" si no tiene nada
+    pub fn label(&self, a: Action) -> String {
+        let list = self.get(a);
+        if list.is_empty() { return "---".to_string(); }
+        list.iter().map(|b| b.label()).collect::<Vec<_>>().join("/")
+    }
+
+    /// Entrada a mostrar en la ayuda: la del mando si hay uno conectado,
+    /// si no la primera de teclado/mouse (o la que haya)
+    fn help_label(&self, a: Action) -> Option<&'static str> {
+        let list = self.get(a);
+        list.iter().find(|b| b.is_pad() == self.pad)
+            .or(list.first())
+            .map(|b| b.label())
+    }
+
+    /// Texto de ayuda a partir de los controles activos:
+    /// cada grupo sale como "W/S=adelante/atrás". Se omiten los grupos sin entradas.
+    pub fn help_text(&self, groups: &[(&[Action], &str)]) -> String {
+        groups.iter()
+            .filter_map(|(actions, what)| {
+                let keys: Option<Vec<&str>> = actions.iter().map(|a| self.help_label(*a)).collect();
+                keys.map(|k| format!("{}={}", k.join("/"), what))
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+/// Ayuda del HUD en partida
+pub const HUD_HELP: &[(&[Action], &str)] = &[
+    (&[Action::MoveForward, Action::MoveBack], "adelante/atrás"),
+    (&[Action::StrafeLeft, Action::StrafeRight], "lateral"),
+    (&[Action::TurnLeft, Action::TurnRight], "girar"),
+    (&[Action::LookUp, Action::LookDown], "mirar"),
+    (&[Action::Sprint], "correr"),
+    (&[Action::Jump], "saltar"),
+    (&[Action::Crouch], "agacharse"),
+    (&[Action::Fire], "disparar"),
+    (&[Action::Reload], "recargar"),
+    (&[Action::Interact], "interactuar"),
+    (&[Action::Slot1, Action::Slot2, Action::Slot3, Action::Slot4, Action::Slot5], "slot"),
+    (&[Action::Unarmed], "vacío"),
+    (&[Action::PrevSlot, Action::NextSlot], "cambiar slot"),
+    (&[Action::UseConsumable], "usar consumible"),
+];
+
+/// Resumen corto para el menú
+pub const MENU_HELP: &[(&[Action], &str)] = &[
+    (&[Action::MoveForward, Action::MoveBack], "avanzar"),
+    (&[Action::StrafeLeft, Action::StrafeRight], "lateral"),
+    (&[Action::Fire], "disparar"),
+    (&[Action::Slot1, Action::Slot2, Action::Slot3, Action::Slot4, Action::Slot5, Action::Unarmed], "slots"),
+    (&[Action::ToggleMap], "minimapa"),
+];
+
+/// Pantallas con botones en fila (victoria, game over)
+pub const CHOICE_HELP: &[(&[Action], &str)] = &[
+    (&[Action::MenuLeft, Action::MenuRight], "elegir"),
+    (&[Action::MenuConfirm], "aceptar"),
+];
+
+/// Pantalla de controles del menú
+pub const CONTROLS_HELP: &[(&[Action], &str)] = &[
+    (&[Action::MenuUp, Action::MenuDown], "elegir"),
+    (&[Action::MenuConfirm], "reasignar"),
+    (&[Action::MenuClear], "de fábrica"),
+    (&[Action::MenuBack], "guardar y volver"),
+];
@@ -2,15 +2,33 @@ mod consts;
 mod types;
 mod player;
 mod ai;
+mod collision;
 mod render;
 mod map;
 mod hud;
 mod audio;
 use audio::Audio;
+mod input;
+use input::{Action, Bindings, HUD_HELP};
+mod decals;
+use decals::{Decals, DecalKind};
+mod particles;
+use particles::Particles;
+mod screen_fx;
+use screen_fx::ScreenFx;
 mod menu; // ⬅️ arriba junto a tus otros mod
 use menu::{Menu, MenuAction};
 mod victory; // nuevo
 use victory::{VictoryScreen, VictoryAction};
+mod game_over;
+use game_over::{GameOverScreen, GameOverAction};
+mod checkpoint;
+use checkpoint::Checkpoints;
+mod projectiles;
+use projectiles::Projectiles;
+mod weapons;
+use weapons::WeaponCatalog;
+mod melee;
 
 
 
@@ -25,14 +43,15 @@ use rand::seq::SliceRandom;
 use crate::consts::*;
 
 use types::{
-    Player, Enemy, EnemyState, Weapon, WeaponType, Rarity,
+    Player, Enemy, EnemyState, HitZone, HitZones, Weapon, FireMode, Rarity,
     AmmoType, Pickup, PickupKind,
     Decoration, DecoKind, Chest,
-    Item, SlotItem, ConsumableType, WeaponState,  
+    Item, SlotItem, ConsumableType, MeleeType, WeaponState, Viewport,
 };
-use player::{handle_input, MoveCfg};
+use player::{handle_input, update_weapon_bob, MoveCfg};
 use ai::{update_enemies, AiCfg};
-use render::{draw_frame, RenderParams};
+use collision::apply_knockback;
+use render::{draw_frame, RenderParams, ViewCfg, camera_fov};
 use map::Map;
 use hud::WeaponHudTextures;
 
@@ -40,7 +59,7 @@ use hud::WeaponHudTextures;
 
 
 #[derive(Clone, Copy, PartialEq, Eq)]
-enum GameState { Menu, Playing, Victory }
+enum GameState { Menu, Playing, Victory, GameOver }
 
 
 
@@ -73,7 +92,8 @@ fn start_use_consumable(player: &Player, slot: &mut SlotItem) -> bool {
             slot.cd = CD_SHIELD_BIG;   // 3.5s
             true
         }
-        Item::Weapon(_, _) => false,
+        // la granada no se “usa”: se lanza manteniendo el disparo
+        Item::Consumable(ConsumableType::Grenade) | Item::Weapon(_, _) | Item::Melee(_) => false,
     }
 }
 
@@ -98,7 +118,7 @@ fn finish_use_consumable(player: &mut Player, slot: &mut SlotItem) {
             player.shield = (player.shield + 50).min(PLAYER_MAX_SHIELD);
             slot.count -= 1;
         }
-        Item::Weapon(_, _) => { /* no aplica */ }
+        Item::Consumable(ConsumableType::Grenade) | Item::Weapon(_, _) | Item::Melee(_) => { /* no aplica */ }
     }
     // termina el uso
     slot.using = false;
@@ -132,6 +152,7 @@ fn make_dropped_from_slot(px: f32, py: f32, slot: &SlotItem) -> Vec<Pickup> {
                 ConsumableType::HealthBig   => (Color::GOLD,    PickupKind::HealthBig),
                 ConsumableType::ShieldSmall => (Color::SKYBLUE, PickupKind::ShieldSmall),
                 ConsumableType::ShieldBig   => (Color::BLUE,    PickupKind::ShieldBig),
+                ConsumableType::Grenade     => (GRENADE_COLOR,  PickupKind::Item { item: Item::Consumable(ConsumableType::Grenade), count: 1 }),
             };
             for _ in 0..slot.count.max(0) {
                 out.push(Pickup {
@@ -144,6 +165,16 @@ fn make_dropped_from_slot(px: f32, py: f32, slot: &SlotItem) -> Vec<Pickup> {
                 });
             }
         }
+        Item::Melee(m) => {
+            out.push(Pickup {
+                x: px + rng.gen_range(-0.25..0.25),
+                y: py + rng.gen_range(-0.25..0.25),
+                kind: PickupKind::Item { item: Item::Melee(m), count: 1 },
+                radius: 0.35,
+                color: MELEE_COLOR,
+                spawn_lock: 0.35,
+            });
+        }
     }
     out
 }
@@ -154,6 +185,7 @@ fn max_stack_and_cd(c: ConsumableType) -> (i32, f32) {
         ConsumableType::HealthBig   => (MAX_STACK_HEALTH_BIG,   CD_HEALTH_BIG),
         ConsumableType::ShieldSmall => (MAX_STACK_SHIELD_SMALL, CD_SHIELD_SMALL),
         ConsumableType::ShieldBig   => (MAX_STACK_SHIELD_BIG,   CD_SHIELD_BIG),
+        ConsumableType::Grenade     => (MAX_STACK_GRENADE,      0.0),
     }
 }
 
@@ -201,7 +233,7 @@ fn try_stack_or_replace_slot(
 
     // Si el nuevo ítem es diferente, entra limpio (sin recarga/uso)
     match &mut new_item.item {
-        Item::Consumable(_) => {
+        Item::Consumable(_) | Item::Melee(_) => {
             new_item.using = false;
             new_item.cd = 0.0;
         }
@@ -220,12 +252,19 @@ fn try_stack_or_replace_slot(
 }
 
 
-fn roll_weapon_type<R: Rng>(rng: &mut R) -> WeaponType {
-    use WeaponType::*;
-    let all = [Pistol, SMG, Rifle, Shotgun, RocketLauncher];
-    *all.choose(rng).unwrap()
+// Pantalla completa al tamaño del monitor; al salir se vuelve al tamaño inicial.
+fn toggle_fullscreen(rl: &mut RaylibHandle) {
+    if rl.is_window_fullscreen() {
+        rl.toggle_fullscreen();
+        rl.set_window_size(SCREEN_W, SCREEN_H);
+    } else {
+        let mon = get_current_monitor();
+        rl.set_window_size(get_monitor_width(mon), get_monitor_height(mon));
+        rl.toggle_fullscreen();
+    }
 }
 
+
 fn roll_rarity<R: Rng>(rng: &mut R) -> Rarity {
     let t: f32 = rng.gen_range(0.0..1.0); // usa gen_range para evitar el conflicto con gen en 2024 edition
     let c  = crate::consts::P_RARITY_COMMON;
@@ -242,33 +281,10 @@ fn roll_rarity<R: Rng>(rng: &mut R) -> Rarity {
 
 
 
-// --------------------------- MAIN ---------------------------
-
-fn main() {
-
-    let (mut rl, thread) = raylib::init()
-        .size(SCREEN_W, SCREEN_H)
-        .title("Raycasting - Slots unificados (armas + consumibles)")
-        .build();
-    rl.set_target_fps(60);
-
-    let map = Map::from_txt("assets/map.txt").expect("No se pudo cargar assets/map.txt");
-
-    let mut minimap = hud::Minimap::new();
-    let mut elapsed: f32 = 0.0;
-    let mut kills: u32 = 0;
-
-    // Catálogo base de armas (rarezas por defecto Común)
-    let weapons_catalog: Vec<Weapon> = vec![
-        Weapon { wtype: WeaponType::Pistol, name: "Pistola", damage: 25, fire_interval: 0.35, mag_size: 12, reload_time: 1.2, ammo_type: AmmoType::Light, rarity: Rarity::Common },
-        Weapon { wtype: WeaponType::SMG,    name: "SMG",     damage: 12, fire_interval: 0.08, mag_size: 30, reload_time: 1.6, ammo_type: AmmoType::Light, rarity: Rarity::Common },
-        Weapon { wtype: WeaponType::Rifle,  name: "Rifle",   damage: 35, fire_interval: 0.50, mag_size: 10, reload_time: 2.0, ammo_type: AmmoType::Medium, rarity: Rarity::Common },
-        Weapon { wtype: WeaponType::Shotgun,name: "Escopeta",damage: 50, fire_interval: 0.80, mag_size: 6,  reload_time: 1.8, ammo_type: AmmoType::Shell,  rarity: Rarity::Common },
-        Weapon { wtype: WeaponType::RocketLauncher, name: "Lanzacohetes", damage: 120, fire_interval: 1.20, mag_size: 1, reload_time: 2.3, ammo_type: AmmoType::Rocket, rarity: Rarity::Common },
-        
-    ];
-
-    // Estado inicial
+// Estado inicial de una partida a partir del mapa: jugador (con la primera arma del catálogo en el slot 1),
+// enemigos, pickups fijos y aleatorios, decoraciones y cofres.
+// Se usa al empezar y al reintentar / reiniciar desde game over o victoria.
+fn new_run(map: &Map, weapons_catalog: &WeaponCatalog) -> (Player, Vec<Enemy>, Vec<Pickup>, Vec<Decoration>, Vec<Chest>) {
     let (px, py) = map.player_spawn.unwrap_or((2.5, 2.5));
     let mut player = Player {
         x: px, y: py, angle: 0.0,
@@ -277,27 +293,39 @@ fn main() {
         ammo_reserve: [60, 40, 20, 12, 4],
         slots: [None, None, None, None, None],
         selected: None,
-        punch_cd: 0.0,
+        melee_cd: 0.0,
+        grenade_charge: 0.0,
+        bob_t: 0.0,
+        bob_amp: 0.0,
+        draw_t: 0.0,
+        pitch: 0.0,
+        eye_z: 0.5,
+        jump_z: 0.0,
+        vz: 0.0,
+        crouch: 0.0,
+        stamina: 100.0,
+        stamina_cd: 0.0,
+        sprinting: false,
+        move_mods: Vec::new(),
+        knock: (0.0, 0.0),
     };
 
-    // Arranca con pistola en slot 1
-    if let Some(pistol) = weapons_catalog.iter().find(|w| w.wtype == WeaponType::Pistol) {
-        let ws = WeaponState { ammo_in_mag: pistol.mag_size, weapon_cd: 0.0, reloading: false, reload_cd: 0.0 };
-        player.slots[0] = Some(SlotItem { item: Item::Weapon(*pistol, ws), count: 1, cd: 0.0, using: false });
-        player.selected = Some(0);
-    }
+    // Arranca con la primera arma del catálogo (la pistola) en slot 1
+    let start = weapons_catalog.starting();
+    let ws = WeaponState::new(&start);
+    player.slots[0] = Some(SlotItem { item: Item::Weapon(start, ws), count: 1, cd: 0.0, using: false });
+    player.selected = Some(0);
 
 
     let mut rng = thread_rng();
     // Enemigos
-    let mut enemies: Vec<Enemy> = map.enemy_spawns.iter().enumerate().map(|(i, &(ex, ey))| {
+    let enemies: Vec<Enemy> = map.enemy_spawns.iter().enumerate().map(|(i, &(ex, ey))| {
         let hp = rng.gen_range(100..=200);
-         let weapon = *weapons_catalog
-            .choose(&mut rng)
-            .expect("weapons_catalog vacío");
+        let weapon = weapons_catalog.base(weapons_catalog.random(&mut rng));
         Enemy { x: ex, y: ey, hp, speed: 1.0,
                 state: EnemyState::Idle, color: Color::ORANGE,
-                weapon, weapon_cd: 0.0, flash_timer: 0.0 , death_anim_t: 0.0,}
+                weapon, weapon_cd: 0.0, flash_timer: 0.0 , death_anim_t: 0.0, knock: (0.0, 0.0),
+                zones: HitZones::SOLDIER }
     }).collect::<Vec<_>>();
 
     // Pickups iniciales desde mapa
@@ -343,7 +371,7 @@ for &(x, y) in &map.weapon_random_spawns {
     }
 
     // elige arma y rareza
-    let wtype = roll_weapon_type(&mut rng);
+    let wtype = weapons_catalog.random(&mut rng);
     let rarity = roll_rarity(&mut rng);
     let color = rarity.color(); // ya lo usas para “brillo”/HUD
 
@@ -437,14 +465,26 @@ for &(x, y) in &map.ammo_random_spawns {
     pickups.extend(map.ammo_shell_spawns.iter().map(|&(x,y)|   Pickup { x, y, kind: PickupKind::Ammo{ ammo: AmmoType::Shell,  amount: AMMO_SHELL_PACK },  radius: 0.35, color: Color::BROWN,     spawn_lock: 0.0 }));
     pickups.extend(map.ammo_rocket_spawns.iter().map(|&(x,y)|  Pickup { x, y, kind: PickupKind::Ammo{ ammo: AmmoType::Rocket, amount: AMMO_ROCKET_PACK }, radius: 0.35, color: Color::RED,       spawn_lock: 0.0 }));
 
-    // Armas en suelo (A/M/R/O/K del mapa)
+    // Armas en suelo (letra `glyph` de cada arma del catálogo)
     let ground_rarity = Rarity::Common;
-    let color_for = |r: Rarity| r.color();
-    pickups.extend(map.weapon_pistol_spawns.iter().map(|&(x,y)|  Pickup { x, y, kind: PickupKind::Weapon{ wtype: WeaponType::Pistol,         rarity: ground_rarity }, radius: 0.35, color: color_for(ground_rarity), spawn_lock: 0.0 }));
-    pickups.extend(map.weapon_smg_spawns.iter().map(|&(x,y)|     Pickup { x, y, kind: PickupKind::Weapon{ wtype: WeaponType::SMG,            rarity: ground_rarity }, radius: 0.35, color: color_for(ground_rarity), spawn_lock: 0.0 }));
-    pickups.extend(map.weapon_rifle_spawns.iter().map(|&(x,y)|   Pickup { x, y, kind: PickupKind::Weapon{ wtype: WeaponType::Rifle,          rarity: ground_rarity }, radius: 0.35, color: color_for(ground_rarity), spawn_lock: 0.0 }));
-    pickups.extend(map.weapon_shotgun_spawns.iter().map(|&(x,y)| Pickup { x, y, kind: PickupKind::Weapon{ wtype: WeaponType::Shotgun,        rarity: ground_rarity }, radius: 0.35, color: color_for(ground_rarity), spawn_lock: 0.0 }));
-    pickups.extend(map.weapon_rocket_spawns.iter().map(|&(x,y)|  Pickup { x, y, kind: PickupKind::Weapon{ wtype: WeaponType::RocketLauncher, rarity: ground_rarity }, radius: 0.35, color: color_for(ground_rarity), spawn_lock: 0.0 }));
+    for &(ch, x, y) in &map.glyph_spawns {
+        if let Some(wtype) = weapons_catalog.by_glyph(ch) {
+            pickups.push(Pickup { x, y, kind: PickupKind::Weapon { wtype, rarity: ground_rarity }, radius: 0.35, color: ground_rarity.color(), spawn_lock: 0.0 });
+        }
+    }
+
+    // Cuchillos y bates en el suelo
+    let melee_spawns = map.knife_spawns.iter().map(|&p| (MeleeType::Knife, p))
+        .chain(map.bat_spawns.iter().map(|&p| (MeleeType::Bat, p)));
+    for (m, (x, y)) in melee_spawns {
+        pickups.push(Pickup { x, y, kind: PickupKind::Item { item: Item::Melee(m), count: 1 }, radius: 0.35, color: MELEE_COLOR, spawn_lock: 0.0 });
+    }
+
+    // Granadas en el suelo
+    for &(x, y) in &map.grenade_spawns {
+        let kind = PickupKind::Item { item: Item::Consumable(ConsumableType::Grenade), count: GRENADE_PACK };
+        pickups.push(Pickup { x, y, kind, radius: 0.35, color: GRENADE_COLOR, spawn_lock: 0.0 });
+    }
 
     // Decoraciones
     let mut decorations: Vec<Decoration> = Vec::new();
@@ -456,16 +496,69 @@ for &(x, y) in &map.ammo_random_spawns {
     }
 
     // Cofres
-    let mut chests: Vec<Chest> = map.chest_spawns.iter().map(|&(x,y)| Chest {
+    let chests: Vec<Chest> = map.chest_spawns.iter().map(|&(x,y)| Chest {
         x, y, radius: 0.33, opened: false, color_closed: Color::BROWN, color_opened: Color::GOLD
     }).collect::<Vec<_>>();
 
-    // Cámara
-    let fov: f32 = 60.0_f32.to_radians();
-    let proj_dist: f32 = (SCREEN_W as f32 / 2.0) / (fov / 2.0).tan();
+    (player, enemies, pickups, decorations, chests)
+}
+
+
+// --------------------------- MAIN ---------------------------
+
+fn main() {
+
+    let (mut rl, thread) = raylib::init()
+        .size(SCREEN_W, SCREEN_H)
+        .resizable()
+        .title("Raycasting - Slots unificados (armas + consumibles)")
+        .build();
+    rl.set_target_fps(60);
+    rl.set_window_min_size(MIN_SCREEN_W, MIN_SCREEN_H);
+    // ESC no cierra la ventana: cada pantalla decide (MenuBack vuelve, Quit sale de la partida)
+    rl.set_exit_key(None);
+
+    let map = Map::from_txt("assets/map.txt").expect("No se pudo cargar assets/map.txt");
+
+    let mut minimap = hud::Minimap::new();
+    let mut elapsed: f32 = 0.0;
+    let mut kills: u32 = 0;
+    let mut headshots: u32 = 0; // kills rematados con un tiro a la cabeza
+
+    // Catálogo de armas (stats base en rareza Común, assets y dónde aparecen)
+    let weapons_catalog = WeaponCatalog::load(WEAPONS_PATH).unwrap_or_else(|e| panic!("{e}"));
+
+    // Estado inicial
+    let (mut player, mut enemies, mut pickups, mut decorations, mut chests) = new_run(&map, &weapons_catalog);
+
+    // Resolución interna de la vista 3D (p.ej. 200 de alto para look retro) y filtro al escalar
+    let view_cfg = if std::path::Path::new(VIDEO_PATH).exists() {
+        ViewCfg::load(VIDEO_PATH).unwrap_or_else(|e| {
+            eprintln!("{e} (se usa la vista de fábrica)");
+            ViewCfg::defaults()
+        })
+    } else {
+        ViewCfg::defaults()
+    };
+    let mut vp = Viewport::of(&rl);
+    let mut view_rt = view_cfg.load_target(&mut rl, &thread, vp);
+    let (mut view_w, mut view_h) = view_cfg.internal_size(vp);
+
+    // Cámara (60° horizontales en 16:9; la proyección usa el tamaño interno)
+    let base_fov: f32 = 60.0_f32.to_radians();
+    let (mut fov, mut proj_dist) = camera_fov(base_fov, view_w, view_h);
 
     // Movimiento + IA
-    let move_cfg = MoveCfg { move_speed: 3.0, rot_speed: 2.5 };
+    let move_cfg = MoveCfg {
+        move_speed: 3.0, rot_speed: 2.5,
+        crouch_mult: 0.5, sprint_mult: 1.6,
+        stamina_max: 100.0, stamina_drain: 25.0, stamina_regen: 20.0,
+        stamina_regen_delay: 0.8, sprint_min: 20.0,
+        mouse_look: true, mouse_sens: 0.0025, turn_keys: false,
+        pitch_speed: 0.8, max_pitch: 0.35,
+        eye_stand: 0.5, eye_crouch: 0.3,
+        jump_speed: 2.2, gravity: 9.0,
+    };
     let ai_cfg = AiCfg { detect_radius: 6.0, melee_range: 0.0, melee_dps: 0.0, shoot_range: 7.0 };
 
 
@@ -477,6 +570,9 @@ let wall_paths = [
     "assets/walls/wall05.png",
     "assets/walls/wall06.png",
     "assets/walls/wall07.png",
+    "assets/walls/wall08.png", // rejilla (see-through)
+    "assets/walls/wall09.png", // valla (see-through)
+    "assets/walls/wall10.png", // ventana (see-through)
     // añade más en el orden de sus ids...
 ];
 
@@ -487,6 +583,14 @@ for p in wall_paths.iter() {
     wall_textures.push(tex);
 }
 
+// 🌄 Panorama del cielo (opcional, lo elige el mapa con `@sky <ruta>`)
+let sky_tex: Option<Texture2D> = map.sky_texture.as_ref().map(|p| {
+    let tex = rl.load_texture(&thread, p).expect("No se pudo cargar el panorama del cielo");
+    tex.set_texture_filter(&thread, TextureFilter::TEXTURE_FILTER_BILINEAR);
+    tex.set_texture_wrap(&thread, TextureWrap::TEXTURE_WRAP_REPEAT);
+    tex
+});
+
 
 let tex_hp_small  = rl.load_texture(&thread, "assets/pickups/health_small.png") .expect("hp small tex");
 let tex_hp_big    = rl.load_texture(&thread, "assets/pickups/health_big.png")   .expect("hp big tex");
@@ -499,13 +603,19 @@ for t in [&tex_hp_small, &tex_hp_big, &tex_sh_small, &tex_sh_big] {
 }
 
 
-let tex_w_pistol  = rl.load_texture(&thread, "assets/pickups/pistol.png") .expect("pistol tex");
-let tex_w_smg     = rl.load_texture(&thread, "assets/pickups/smg.png")    .expect("smg tex");
-let tex_w_rifle   = rl.load_texture(&thread, "assets/pickups/rifle.png")  .expect("rifle tex");
-let tex_w_shotgun = rl.load_texture(&thread, "assets/pickups/shotgun.png").expect("shotgun tex");
-let tex_w_rocket  = rl.load_texture(&thread, "assets/pickups/rocket.png") .expect("rocket tex");
+// Icono en el suelo de cada arma del catálogo (índice = `WeaponType`)
+let tex_weapons: Vec<Texture2D> = weapons_catalog.iter()
+    .map(|w| rl.load_texture(&thread, &w.icon).unwrap_or_else(|_| panic!("falta {}", w.icon)))
+    .collect();
 
-for t in [&tex_w_pistol, &tex_w_smg, &tex_w_rifle, &tex_w_shotgun, &tex_w_rocket] {
+for t in &tex_weapons {
+    t.set_texture_filter(&thread, TextureFilter::TEXTURE_FILTER_BILINEAR);
+}
+
+let tex_knife = rl.load_texture(&thread, "assets/pickups/knife.png").expect("knife tex");
+let tex_bat   = rl.load_texture(&thread, "assets/pickups/bat.png")  .expect("bat tex");
+let tex_grenade = rl.load_texture(&thread, "assets/pickups/grenade.png").expect("grenade tex");
+for t in [&tex_knife, &tex_bat, &tex_grenade] {
     t.set_texture_filter(&thread, TextureFilter::TEXTURE_FILTER_BILINEAR);
 }
 
@@ -554,16 +664,16 @@ for t in [&tex_enemy_alive, &tex_d0, &tex_d1, &tex_d2, &tex_d3] {
 
 
 let weapon_hud_tex = WeaponHudTextures {
-    pistol:   rl.load_texture(&thread, "assets/hud/held_pistol.png").expect("falta held_pistol.png"),
-    smg:      rl.load_texture(&thread, "assets/hud/held_smg.png").expect("falta held_smg.png"),
-    rifle:    rl.load_texture(&thread, "assets/hud/held_rifle.png").expect("falta held_rifle.png"),
-    shotgun:  rl.load_texture(&thread, "assets/hud/held_shotgun.png").expect("falta held_shotgun.png"),
-    rocket:   rl.load_texture(&thread, "assets/hud/held_rocket.png").expect("falta held_rocket.png"),
+    held: weapons_catalog.iter()
+        .map(|w| rl.load_texture(&thread, &w.held).unwrap_or_else(|_| panic!("falta {}", w.held)))
+        .collect(),
+    knife: rl.load_texture(&thread, "assets/hud/held_knife.png").expect("falta held_knife.png"),
+    bat:   rl.load_texture(&thread, "assets/hud/held_bat.png").expect("falta held_bat.png"),
 };
   
 
 
-let audio = Audio::new();
+let audio = Audio::new(&weapons_catalog);
 
 // Armar el paquete para el render
 let enemy_tex = render::EnemyTextures {
@@ -584,58 +694,86 @@ let pickup_tex = render::PickupTextures {
     shield_small: Some(&tex_sh_small),
     shield_big:   Some(&tex_sh_big),
 
-    weapon_pistol:  Some(&tex_w_pistol),
-    weapon_smg:     Some(&tex_w_smg),
-    weapon_rifle:   Some(&tex_w_rifle),
-    weapon_shotgun: Some(&tex_w_shotgun),
-    weapon_rocket:  Some(&tex_w_rocket),
+    weapons: tex_weapons.iter().collect(),
+    knife: Some(&tex_knife),
+    bat:   Some(&tex_bat),
+    grenade: Some(&tex_grenade),
     ammo_generic: Some(&tex_ammo),
     
 };
 
  
 let mut state   = GameState::Menu;   // si no lo tenías
+// Controles: assets/controls.txt si existe, si no los de fábrica
+let mut bindings = if std::path::Path::new(CONTROLS_PATH).exists() {
+    Bindings::load(CONTROLS_PATH).unwrap_or_else(|e| {
+        eprintln!("{e} (se usan los controles de fábrica)");
+        Bindings::defaults()
+    })
+} else {
+    Bindings::defaults()
+};
 let mut menu    = Menu::new();       // ya lo usas
 let mut victory = VictoryScreen::new();
+let mut game_over = GameOverScreen::new();
+let mut fresh_run = false;             // rearmar la partida antes de volver a jugar
+let mut death_t: f32 = 0.0;            // tiempo desde que murió el jugador (cámara cayendo)
+let mut checkpoints = Checkpoints::new(&map);
+let mut lives = PLAYER_LIVES;          // reapariciones en checkpoint que quedan
+let mut checkpoint_msg_t: f32 = 0.0;   // aviso "CHECKPOINT" en pantalla
+let mut decals  = Decals::new();       // agujeros de bala / quemaduras en paredes
+let mut particles = Particles::new();  // sangre, polvo, brillos…
+let mut projectiles = Projectiles::new(); // cohetes en vuelo
+let mut screen_fx = ScreenFx::new();   // viñeta de daño, marca de impacto, flashes
 
     while !rl.window_should_close() {
     let dt = rl.get_frame_time();
+    bindings.update(&rl); // sticks del mando (antes de consultar acciones)
+
+    // Pantalla completa (F11 por defecto) y cambios de tamaño de ventana
+    if bindings.pressed(&rl, Action::Fullscreen) {
+        toggle_fullscreen(&mut rl);
+    }
+    let new_vp = Viewport::of(&rl);
+    if new_vp != vp {
+        vp = new_vp;
+        view_rt = view_cfg.load_target(&mut rl, &thread, vp);
+        (view_w, view_h) = view_cfg.internal_size(vp);
+        (fov, proj_dist) = camera_fov(base_fov, view_w, view_h);
+    }
 
     // ----- ESTADO: MENÚ -----
 
 
     // ----- ESTADO: MENÚ -----
     if state == GameState::Menu {
-        match menu.handle_input(&rl) {
-            MenuAction::Start => { state = GameState::Playing; }
+        match menu.handle_input(&mut rl, &mut bindings) {
+            MenuAction::Start => {
+                state = GameState::Playing;
+                rl.disable_cursor(); // capturar el mouse para mirar
+            }
+            MenuAction::Quit => break,
             MenuAction::None => {}
         }
 
         let mut d = rl.begin_drawing(&thread);
         d.clear_background(Color::BLACK);
-        menu.draw(&mut d);
+        menu.draw(&mut d, &bindings);
         continue; // seguimos al siguiente frame del while
     }
 
     // ----- ESTADO: VICTORY -----
 if state == GameState::Victory {
     // Entrada (fuera de begin_drawing)
-    match victory.handle_input(&rl) {
+    match victory.handle_input(&rl, &bindings) {
         VictoryAction::ToMenu => {
             state = GameState::Menu;
-            // Si quieres limpiar stats al volver al menú:
-             elapsed = 0.0; kills = 0;
+            fresh_run = true;
         }
         VictoryAction::Restart => {
-            // Opción simple: también vuelve al menú (más seguro si aún no
-            // extrajiste tu setup a una función).
-            state = GameState::Menu;
-
-            // Si ya tienes una función de setup del run (recomendada),
-            // llámala aquí y pon Playing:
-            // setup_run(&mut player, &mut enemies, &mut pickups, &mut decorations, &mut chests, &map, &weapons_catalog, &mut rng);
-            // elapsed = 0.0; kills = 0;
-            // state = GameState::Playing;
+            state = GameState::Playing;
+            fresh_run = true;
+            rl.disable_cursor();
         }
         VictoryAction::None => {}
     }
@@ -643,15 +781,71 @@ if state == GameState::Victory {
     // Dibujo
     let mut d = rl.begin_drawing(&thread);
     d.clear_background(Color::BLACK);
-    victory.draw(&mut d);
+    victory.draw(&mut d, &bindings);
     continue;
 }
 
+    // ----- ESTADO: GAME OVER -----
+if state == GameState::GameOver {
+    match game_over.handle_input(&rl, &bindings) {
+        GameOverAction::ToMenu => {
+            state = GameState::Menu;
+            fresh_run = true;
+        }
+        GameOverAction::Retry => {
+            state = GameState::Playing;
+            fresh_run = true;
+            rl.disable_cursor();
+        }
+        // Reaparecer en el último checkpoint (gasta una vida)
+        GameOverAction::Checkpoint => {
+            if lives > 0 && checkpoints.restore(&mut player, &mut enemies, &mut pickups, &mut chests, &mut kills, &mut headshots) {
+                lives -= 1;
+                death_t = 0.0;
+                projectiles.clear();
+                screen_fx = ScreenFx::new();
+                state = GameState::Playing;
+                rl.disable_cursor();
+            }
+        }
+        GameOverAction::None => {}
+    }
+
+    let mut d = rl.begin_drawing(&thread);
+    d.clear_background(Color::BLACK);
+    game_over.draw(&mut d, &bindings);
+    continue;
+}
+
+    // Partida nueva pendiente (reiniciar / reintentar / volver a empezar desde el menú)
+    if fresh_run {
+        (player, enemies, pickups, decorations, chests) = new_run(&map, &weapons_catalog);
+        decals = Decals::new();
+        particles = Particles::new();
+        projectiles.clear();
+        screen_fx = ScreenFx::new();
+        elapsed = 0.0;
+        kills = 0;
+        headshots = 0;
+        death_t = 0.0;
+        checkpoints = Checkpoints::new(&map);
+        lives = PLAYER_LIVES;
+        fresh_run = false;
+    }
+
+    // Salir del juego desde la partida (ESC por defecto)
+    if bindings.pressed(&rl, Action::Quit) {
+        break;
+    }
+
         
         let dt = rl.get_frame_time();
-        elapsed += dt;
+        // Muerto: no se controla nada mientras cae la cámara (y el tiempo deja de contar)
+        let alive = player.hp > 0;
+        if alive { elapsed += dt; }
 
-        player.punch_cd = (player.punch_cd - dt).max(0.0);
+        player.melee_cd = (player.melee_cd - dt).max(0.0);
+        player.draw_t = (player.draw_t - dt).max(0.0);
         audio.update();
 
 
@@ -670,14 +864,24 @@ for i in 0..player.slots.len() {
             if slot.cd <= 0.0 {
                 // Ahora sí podemos mutar player y el slot al mismo tiempo,
                 // porque el slot NO está dentro de player.slots (lo sacamos con take()).
+                let before = slot.count;
                 finish_use_consumable(&mut player, &mut slot);
+                if slot.count < before {
+                    // ✨ brillo frente a la cámara (vida = verde, escudo = azul)
+                    let fx = match slot.item {
+                        Item::Consumable(ConsumableType::HealthSmall | ConsumableType::HealthBig) => &particles::HEAL_SPARKLE,
+                        _ => &particles::SHIELD_SPARKLE,
+                    };
+                    let (fx_x, fx_y) = (player.x + player.angle.cos() * 0.6, player.y + player.angle.sin() * 0.6);
+                    particles.emit(fx, fx_x, fx_y, 0.15, player.angle);
+                }
             }
         }
         // decide si el slot sigue existiendo
         let keep = match slot.item {
             // si quieres que al llegar a 0 unidades se borre del slot:
             Item::Consumable(_) => slot.count > 0 || slot.using,
-            Item::Weapon(_, _)  => true,
+            Item::Weapon(_, _) | Item::Melee(_) => true,
         };
         if keep {
             player.slots[i] = Some(slot);
@@ -688,20 +892,49 @@ for i in 0..player.slots.len() {
 
 
         // Movimiento (con colisiones con decoraciones/cofres)
-        handle_input(&rl, &mut player, &move_cfg, dt, &map, &decorations, &chests);
+        // Penalizaciones de velocidad según lo que se tiene en la mano
+        match player.selected.and_then(|si| player.slots[si]) {
+            Some(SlotItem { item: Item::Consumable(_), using: true, .. }) => {
+                player.set_move_mod("consumible", CONSUME_MOVE_MULT, None);
+                player.clear_move_mod("arma");
+            }
+            Some(SlotItem { item: Item::Weapon(w, _), .. }) => {
+                player.clear_move_mod("consumible");
+                player.set_move_mod("arma", w.move_mult, None);
+            }
+            _ => {
+                player.clear_move_mod("consumible");
+                player.clear_move_mod("arma");
+            }
+        }
+
+        let (prev_x, prev_y) = (player.x, player.y);
+        if alive {
+            handle_input(&rl, &bindings, &mut player, &move_cfg, dt, &map, &decorations, &chests);
+        }
+        let moved = ((player.x - prev_x).powi(2) + (player.y - prev_y).powi(2)).sqrt();
+        update_weapon_bob(&mut player, moved, dt, &move_cfg);
+
+        // Empuje de explosiones (no cuenta para el balanceo del arma)
+        (player.x, player.y) = apply_knockback(&map, &decorations, &chests, player.x, player.y, &mut player.knock, PLAYER_RADIUS, dt);
 
 
         let prev_selected = player.selected;
 
-         minimap.handle_input(&rl);
+         minimap.handle_input(&rl, &bindings);
 
         // Selección de slots
-        if rl.is_key_pressed(KeyboardKey::KEY_ZERO)  { player.selected = None; }
-        if rl.is_key_pressed(KeyboardKey::KEY_ONE)   { player.selected = Some(0); }
-        if rl.is_key_pressed(KeyboardKey::KEY_TWO)   { player.selected = Some(1); }
-        if rl.is_key_pressed(KeyboardKey::KEY_THREE) { player.selected = Some(2); }
-        if rl.is_key_pressed(KeyboardKey::KEY_FOUR)  { player.selected = Some(3); }
-        if rl.is_key_pressed(KeyboardKey::KEY_FIVE)  { player.selected = Some(4); }
+        if alive {
+            if bindings.pressed(&rl, Action::Unarmed) { player.selected = None; }
+            if bindings.pressed(&rl, Action::Slot1)   { player.selected = Some(0); }
+            if bindings.pressed(&rl, Action::Slot2)   { player.selected = Some(1); }
+            if bindings.pressed(&rl, Action::Slot3)   { player.selected = Some(2); }
+            if bindings.pressed(&rl, Action::Slot4)   { player.selected = Some(3); }
+            if bindings.pressed(&rl, Action::Slot5)   { player.selected = Some(4); }
+            // Bumpers del mando: recorrer los slots ocupados
+            if bindings.pressed(&rl, Action::NextSlot) { player.cycle_slot(1); }
+            if bindings.pressed(&rl, Action::PrevSlot) { player.cycle_slot(-1); }
+        }
 
         if player.selected != prev_selected {
             if let Some(si) = prev_selected {
@@ -712,6 +945,7 @@ for i in 0..player.slots.len() {
                             slot.using = false;
                             slot.cd = 0.0;
                         }
+                        Item::Melee(_) => {}
                         // ❌ Arma: cancelar recarga en curso
                         Item::Weapon(_, ws) => {
                             if ws.reloading {
@@ -725,16 +959,23 @@ for i in 0..player.slots.len() {
                     player.slots[si] = Some(slot);
                 }
             }
+
+            // Sacar el arma nueva: sube desde abajo y no dispara hasta terminar
+            player.draw_t = match player.selected.and_then(|si| player.slots[si].as_ref()) {
+                Some(SlotItem { item: Item::Weapon(w, _), .. }) => w.draw_time,
+                _ => 0.0,
+            };
         }
 
-        let fire_down        = rl.is_key_down(KeyboardKey::KEY_SPACE);
-        let reload_pressed   = rl.is_key_pressed(KeyboardKey::KEY_R);
-        let interact_pressed = rl.is_key_pressed(KeyboardKey::KEY_E);
+        let fire_down        = alive && bindings.down(&rl, Action::Fire);
+        let reload_pressed   = alive && bindings.pressed(&rl, Action::Reload);
+        let mode_pressed     = alive && bindings.pressed(&rl, Action::FireMode);
+        let interact_pressed = alive && bindings.pressed(&rl, Action::Interact);
 
 
         // Usar consumible (F) — sacar y devolver el slot para evitar doble préstamo
         // F (usar consumible)
-if rl.is_key_pressed(KeyboardKey::KEY_F) {
+if alive && bindings.pressed(&rl, Action::UseConsumable) {
     if let Some(si) = player.selected {
         if let Some(mut slot) = player.slots[si].take() {
             let started = start_use_consumable(&player, &mut slot);
@@ -745,13 +986,39 @@ if rl.is_key_pressed(KeyboardKey::KEY_F) {
             // Si el slot sigue existiendo (armas siempre, consumibles con count>0)
             let keep = match slot.item {
                 Item::Consumable(_) => slot.count > 0 || slot.using,
-                Item::Weapon(_, _)  => true,
+                Item::Weapon(_, _) | Item::Melee(_) => true,
             };
             if keep { player.slots[si] = Some(slot); }
         }
     }
 }
 
+        // Granada en la mano: mantener el disparo carga el tiro y soltarlo la lanza
+        // (más carga = más lejos; mirando hacia arriba sale más bombeada)
+        let grenade_slot = player.selected.filter(|&si| matches!(
+            player.slots[si],
+            Some(SlotItem { item: Item::Consumable(ConsumableType::Grenade), count: 1.., .. })
+        ));
+        match grenade_slot {
+            None => player.grenade_charge = 0.0,
+            Some(_) if fire_down && player.draw_t <= 0.0 => {
+                player.grenade_charge = (player.grenade_charge + dt / GRENADE_CHARGE_TIME).min(1.0);
+            }
+            Some(si) if player.grenade_charge > 0.0 => {
+                let speed = GRENADE_MIN_SPEED + (GRENADE_MAX_SPEED - GRENADE_MIN_SPEED) * player.grenade_charge;
+                let vz = speed * (GRENADE_LOFT + player.pitch);
+                projectiles.throw(player.x, player.y, player.eye_z - 0.1, player.angle, speed, vz, GRENADE_DAMAGE);
+                audio.play_swing();
+                player.grenade_charge = 0.0;
+
+                if let Some(slot) = player.slots[si].as_mut() {
+                    slot.count -= 1;
+                    if slot.count <= 0 { player.slots[si] = None; }
+                }
+            }
+            Some(_) => {}
+        }
+
 
 
 
@@ -766,8 +1033,9 @@ if rl.is_key_pressed(KeyboardKey::KEY_F) {
                 let dy = c.y - player.y;
                 if (dx*dx + dy*dy).sqrt() <= r {
                     c.opened = true;
+                    particles.emit(&particles::CHEST_SPARKLE, c.x, c.y, 0.3, 0.0);
 
-                    let tabla: [PickupKind; 14] = [
+                    let mut tabla = vec![
                         PickupKind::HealthSmall,
                         PickupKind::HealthBig,
                         PickupKind::ShieldSmall,
@@ -777,12 +1045,14 @@ if rl.is_key_pressed(KeyboardKey::KEY_F) {
                         PickupKind::Ammo { ammo: AmmoType::Heavy,  amount: AMMO_HEAVY_PACK  },
                         PickupKind::Ammo { ammo: AmmoType::Shell,  amount: AMMO_SHELL_PACK  },
                         PickupKind::Ammo { ammo: AmmoType::Rocket, amount: AMMO_ROCKET_PACK },
-                        PickupKind::Weapon { wtype: WeaponType::Pistol,         rarity: Rarity::Uncommon },
-                        PickupKind::Weapon { wtype: WeaponType::SMG,            rarity: Rarity::Rare },
-                        PickupKind::Weapon { wtype: WeaponType::Rifle,          rarity: Rarity::Rare },
-                        PickupKind::Weapon { wtype: WeaponType::Shotgun,        rarity: Rarity::Epic },
-                        PickupKind::Weapon { wtype: WeaponType::RocketLauncher, rarity: Rarity::Legendary },
+                        PickupKind::Item { item: Item::Melee(MeleeType::Knife), count: 1 },
+                        PickupKind::Item { item: Item::Melee(MeleeType::Bat),   count: 1 },
+                        PickupKind::Item { item: Item::Consumable(ConsumableType::Grenade), count: GRENADE_PACK },
                     ];
+                    // + las armas del catálogo que salen en cofres, con su rareza
+                    tabla.extend(weapons_catalog.iter().filter_map(|d| {
+                        d.chest_rarity.map(|rarity| PickupKind::Weapon { wtype: d.weapon.wtype, rarity })
+                    }));
 
                     let num_drops = rng.gen_range(1..=2);
                     for kind in tabla.choose_multiple(&mut rng, num_drops) {
@@ -805,7 +1075,9 @@ if rl.is_key_pressed(KeyboardKey::KEY_F) {
                                 Item::Consumable(ConsumableType::HealthBig)   => Color::GOLD,
                                 Item::Consumable(ConsumableType::ShieldSmall) => Color::SKYBLUE,
                                 Item::Consumable(ConsumableType::ShieldBig)   => Color::BLUE,
+                                Item::Consumable(ConsumableType::Grenade)     => GRENADE_COLOR,
                                 Item::Weapon(w, _) => w.rarity.color(),
+                                Item::Melee(_) => MELEE_COLOR,
                             },
                         };
 
@@ -875,14 +1147,12 @@ for (i, p) in pickups.iter().enumerate() {
 
         // Armas → slot seleccionado con estado inicial
         PickupKind::Weapon { wtype, rarity } => {
-            if let Some(base) = weapons_catalog.iter().find(|w| w.wtype == wtype) {
-                let w = Weapon { rarity, ..*base };
-                let ws = WeaponState { ammo_in_mag: w.mag_size, weapon_cd: 0.0, reloading: false, reload_cd: 0.0 };
-                let new_slot = SlotItem { item: Item::Weapon(w, ws), count: 1, cd: 0.0 , using: false};
-                let res = try_stack_or_replace_slot(&mut player, new_slot, selected_slot);
-                if res.consumed { to_remove.push(i); }
-                to_add.extend(res.drops);
-            }
+            let w = Weapon { rarity, ..weapons_catalog.base(wtype) };
+            let ws = WeaponState::new(&w);
+            let new_slot = SlotItem { item: Item::Weapon(w, ws), count: 1, cd: 0.0 , using: false};
+            let res = try_stack_or_replace_slot(&mut player, new_slot, selected_slot);
+            if res.consumed { to_remove.push(i); }
+            to_add.extend(res.drops);
         }
 
         // Item genérico
@@ -897,13 +1167,54 @@ for (i, p) in pickups.iter().enumerate() {
 
 // aplicar cambios fuera del bucle
 to_remove.sort_unstable_by(|a,b| b.cmp(a));
+if let Some(&last) = to_remove.last() {
+    screen_fx.on_pickup(pickups[last].color);
+}
 for idx in to_remove { pickups.remove(idx); }
 pickups.extend(to_add);
 
         }
 
         // IA de enemigos
-        update_enemies(&mut enemies, &mut player, dt, &ai_cfg, &map, &decorations, &chests, &audio, );
+        // Checkpoints: al tocar uno nuevo se guarda el estado de la partida
+        checkpoint_msg_t = (checkpoint_msg_t - dt).max(0.0);
+        if alive {
+            if let Some(ci) = checkpoints.update(&player, &enemies, &pickups, &chests, kills, headshots) {
+                let cp = &checkpoints.list[ci];
+                particles.emit(&particles::HEAL_SPARKLE, cp.x, cp.y, 0.7, 0.0);
+                screen_fx.on_pickup(Color::GREEN);
+                checkpoint_msg_t = 2.0;
+            }
+        }
+
+        let landed_grenades = projectiles.landed_grenades();
+        update_enemies(&mut enemies, &mut player, dt, &ai_cfg, &map, &decorations, &chests, &landed_grenades, &audio, &mut screen_fx);
+
+        // Cohetes y granadas: avanzan, los cohetes dejan estela y explotan al chocar;
+        // las granadas rebotan y revientan al acabarse la mecha
+        for ex in projectiles.update(dt, &map, &decorations, &chests, &enemies) {
+            kills += projectiles::explode(&ex, &map, &mut player, &mut enemies, &mut decals, &mut particles, &audio, &mut screen_fx);
+        }
+        for r in projectiles.iter() {
+            particles.emit(&particles::ROCKET_TRAIL, r.x, r.y, r.z, r.dir + std::f32::consts::PI);
+        }
+        particles.update(dt, &map);
+        screen_fx.update(dt);
+
+        // Muerte: la cámara cae al suelo mirando un poco hacia arriba y,
+        // pasado DEATH_CAM_TIME, se muestra la pantalla de game over
+        if player.hp <= 0 {
+            death_t += dt;
+            player.eye_z += (DEATH_EYE_Z - player.eye_z) * (dt * 4.0).min(1.0);
+            player.pitch += (DEATH_PITCH - player.pitch) * (dt * 3.0).min(1.0);
+            player.selected = None; // baja el arma
+
+            if death_t >= DEATH_CAM_TIME {
+                rl.enable_cursor();
+                game_over.set_stats(elapsed, kills, headshots, lives > 0 && checkpoints.has_snapshot());
+                state = GameState::GameOver;
+            }
+        }
 
         // Calcula cuántos enemigos siguen vivos
 let enemies_left = enemies
@@ -912,45 +1223,66 @@ let enemies_left = enemies
     .count();
 
 // Si no queda ninguno y estamos jugando → pasar a Victory
-if enemies_left == 0 && state == GameState::Playing {
-    victory.set_stats(elapsed, kills); // tiempo total y kills
+if enemies_left == 0 && state == GameState::Playing && player.hp > 0 {
+    rl.enable_cursor();
+    victory.set_stats(elapsed, kills, headshots); // tiempo total y kills
     state = GameState::Victory;
     // Saltamos a la rama Victory en el siguiente ciclo (o podrías "continue" aquí)
 }
 
 
-        let attack_down = rl.is_key_down(KeyboardKey::KEY_SPACE);
+        let attack_down = alive && bindings.down(&rl, Action::Fire);
+        let quick_melee = alive && bindings.pressed(&rl, Action::Melee);
         // ---------------- DRAW + DISPARO ----------------
 let mut d = rl.begin_drawing(&thread);
 d.clear_background(Color::BLACK);
 
-let out = draw_frame(
-    &mut d,
-    &thread,
-    &enemies,
-    &RenderParams {
-        fov,
-        proj_dist,
-        player_x: player.x,
-        player_y: player.y,
-        player_angle: player.angle,
-    },
-    &map,
-    &pickups,
-    &decorations,
-    &chests,
-    &wall_textures, // ← NUEVO
-     &pickup_tex,
-     &chest_tex,
-     &enemy_tex,
-);
+// Vista 3D a resolución interna → render target; luego se escala a la ventana
+let out = {
+    let mut td = d.begin_texture_mode(&thread, &mut view_rt);
+    td.clear_background(Color::BLACK);
+    draw_frame(
+        &mut td,
+        &thread,
+        &enemies,
+        &RenderParams {
+            fov,
+            proj_dist,
+            player_x: player.x,
+            player_y: player.y,
+            player_angle: player.angle,
+            view_w,
+            view_h,
+            pitch: player.pitch,
+            eye_z: player.eye_z,
+        },
+        &map,
+        &pickups,
+        &decorations,
+        &chests,
+        &checkpoints.list,
+        &wall_textures, // ← NUEVO
+         &pickup_tex,
+         &chest_tex,
+         &enemy_tex,
+         sky_tex.as_ref(),
+         &decals,
+         &particles,
+         &projectiles,
+    )
+};
+render::blit_view(&mut d, &view_rt, vp);
 
 // Disparo/recarga desde el slot seleccionado (arma) — con take()/devolver
+// cono actual del arma empuñada (para la mira del HUD); sin arma de fuego, la cruz cerrada
+let mut aim_cone: Option<f32> = alive.then_some(0.0);
 if let Some(si) = player.selected {
     if let Some(mut slot) = player.slots[si].take() {
         if let Item::Weapon(w, ref mut ws) = slot.item {
             // Timers
             ws.weapon_cd = (ws.weapon_cd - dt).max(0.0);
+            ws.kick = (ws.kick - dt * 5.0).max(0.0);
+            ws.bloom = (ws.bloom - BLOOM_RECOVERY * dt).max(0.0);
             if ws.reloading {
                 ws.reload_cd -= dt;
                 if ws.reload_cd <= 0.0 {
@@ -977,53 +1309,146 @@ if reload_pressed && !ws.reloading {
 }
 
 
+            // Giro del cañón (ametralladora): sube con el gatillo apretado y baja al
+            // soltarlo; hasta girar del todo no sale ninguna bala
+            let spin_up = w.spin_up;
+            if spin_up > 0.0 {
+                let spinning = fire_down && !ws.reloading && player.draw_t <= 0.0;
+                ws.spin = if spinning {
+                    (ws.spin + dt / spin_up).min(1.0)
+                } else {
+                    (ws.spin - dt / (spin_up * 1.5)).max(0.0)
+                };
+            }
+            let spun = spin_up <= 0.0 || ws.spin >= 1.0;
+
+            // Modo de disparo (B): sólo en las armas que admiten más de uno
+            if mode_pressed && w.fire_modes.len() > 1 {
+                ws.mode = w.next_mode(ws.mode);
+                ws.burst_left = 0;
+            }
+
+            // Gatillo: semi y ráfaga piden soltar entre pulsaciones; una ráfaga empezada sigue sola
+            if !fire_down { ws.trigger_held = false; }
+            if ws.reloading { ws.burst_left = 0; }
+            let trigger = match ws.mode {
+                FireMode::Auto => fire_down,
+                FireMode::Semi | FireMode::Burst => ws.burst_left > 0 || (fire_down && !ws.trigger_held),
+            };
+
+            // Cono de precisión: base del arma + disparos seguidos + movimiento (en el aire, como correr)
+            let moving = if player.jump_z > 0.0 { AIR_SPREAD_MOVE } else { player.bob_amp };
+
             // Disparo (SPACE)
-            if fire_down && !ws.reloading && ws.weapon_cd <= 0.0 {
+            if trigger && !ws.reloading && ws.weapon_cd <= 0.0 && player.draw_t <= 0.0 && player.melee_cd <= 0.0 && spun {
                 if ws.ammo_in_mag > 0 {
                     ws.ammo_in_mag -= 1;
+                    ws.kick = (ws.kick + w.recoil).min(1.0);
 
                     // 🔊 SONIDO DE DISPARO — AQUI
                     audio.play_shot(w.wtype);
 
-                    // Hitscan contra sprites de enemigos
-                    let center = (SCREEN_W / 2) as i32;
-                    let wall_depth = out.zbuffer[center as usize];
-                    let mut best: Option<(usize, f32)> = None;
-
-                    for ds in &out.drawn {
-                        if center >= ds.start_x
-                            && center <= ds.end_x
-                            && ds.depth < wall_depth
-                        {
-                            if best.map_or(true, |(_, bd)| ds.depth < bd) {
-                                best = Some((ds.idx, ds.depth));
+                    if w.projectile {
+                        // Cohete: sale a la altura del arma y explota al chocar (ver projectiles.rs)
+                        projectiles.fire(player.x, player.y, player.eye_z - 0.1, player.angle, w.effective_damage());
+                    } else {
+                        // Hitscan contra sprites de enemigos (coordenadas de la vista interna).
+                        // La mira está en el centro de la vista; con pitch/salto el sprite se mueve en Y.
+                        // Cada perdigón es un rayo en un punto al azar del cono (quieto y sin bloom, 1 rayo al centro).
+                        let center = view_w / 2;
+                        let center_y = view_h / 2;
+                        let mut rng = thread_rng();
+                        let mut hits: Vec<(usize, i32, bool)> = Vec::new(); // (idx_enemigo, daño acumulado, ¿a la cabeza?)
+                        let cone = w.cone(ws.bloom, moving, player.crouch);
+
+                        for _ in 0..w.pellets {
+                            // punto uniforme dentro del cono → desvío en píxeles de la vista
+                            let (ax, ay) = if cone > 0.0 {
+                                let r = cone * rng.r#gen::<f32>().sqrt();
+                                let t = rng.gen_range(0.0..std::f32::consts::TAU);
+                                (r * t.cos(), r * t.sin())
+                            } else {
+                                (0.0, 0.0)
+                            };
+                            let px = (center + (ax.tan() * proj_dist) as i32).clamp(0, view_w - 1);
+                            let py = center_y + (ay.tan() * proj_dist) as i32;
+                            let wall_depth = out.zbuffer[px as usize];
+
+                            let mut best: Option<(usize, f32, HitZone)> = None;
+                            for ds in &out.drawn {
+                                if px >= ds.start_x
+                                    && px <= ds.end_x
+                                    && py >= ds.start_y
+                                    && py <= ds.end_y
+                                    && ds.depth < wall_depth
+                                    && enemies[ds.idx].is_alive()
+                                {
+                                    if best.map_or(true, |(_, bd, _)| ds.depth < bd) {
+                                        // altura relativa dentro del sprite → cabeza / torso / piernas
+                                        let frac = (py - ds.start_y) as f32 / (ds.end_y - ds.start_y).max(1) as f32;
+                                        best = Some((ds.idx, ds.depth, enemies[ds.idx].zones.zone_at(frac)));
+                                    }
+                                }
                             }
-                        }
-                    }
 
-                    if let Some((hit_idx, _)) = best {
-                        if enemies[hit_idx].state != EnemyState::Dead
-                            && enemies[hit_idx].hp > 0
-                        {
-                            // antes de aplicar daño, recordamos si estaba vivo
-                            let was_alive = enemies[hit_idx].hp > 0;
-
-                            enemies[hit_idx].hp -= w.effective_damage();
-                            if enemies[hit_idx].hp <= 0 {
-                                if was_alive {
-                                    enemies[hit_idx].state = EnemyState::Dead;
-                                    kills += 1; // ✅ contamos el kill sólo al pasar a muerto
-                                    audio.play_enemy_death();
+                            if let Some((hit_idx, _, zone)) = best {
+                                let dmg = (w.effective_damage() as f32 * zone.damage_mult()).round() as i32;
+                                let head = zone == HitZone::Head;
+                                match hits.iter_mut().find(|(i, _, _)| *i == hit_idx) {
+                                    Some((_, acc, h)) => { *acc += dmg; *h |= head; }
+                                    None => hits.push((hit_idx, dmg, head)),
                                 }
+                            } else if let Some(hit) = out.wall_hit(px, py as f32) {
+                                // Falló a todos → marca en la pared donde cae el rayo
+                                decals.add(hit, DecalKind::BulletHole);
+
+                                // polvo saliendo de la pared hacia el jugador (zbuffer es distancia perpendicular)
+                                let ray = player.angle + ax;
+                                let dist = wall_depth / ax.cos() - 0.03;
+                                let (ix, iy) = (player.x + ray.cos() * dist, player.y + ray.sin() * dist);
+                                particles.emit(&particles::WALL_DUST, ix, iy, 1.0 - hit.v, ray + std::f32::consts::PI);
+                            }
+                        }
+
+                        // El daño se aplica una vez por enemigo: varios perdigones = un solo golpe
+                        // (un solo kill, un solo sonido) aunque la escopeta lo acribille
+                        if !hits.is_empty() {
+                            screen_fx.on_hit(hits.iter().any(|&(_, _, head)| head));
+                        }
+                        for (hit_idx, dmg, head) in hits {
+                            let e = &mut enemies[hit_idx];
+                            e.hp -= dmg;
+                            if e.hp <= 0 {
+                                e.state = EnemyState::Dead;
+                                kills += 1; // ✅ contamos el kill sólo al pasar a muerto
+                                if head { headshots += 1; }
+                                audio.play_enemy_death();
+                                particles.emit(&particles::GIBS, e.x, e.y, 0.45, player.angle);
                             } else {
-                                enemies[hit_idx].flash_timer = 0.1;
-                                 audio.play_enemy_hurt();
+                                e.flash_timer = 0.1;
+                                audio.play_enemy_hurt();
+                                particles.emit(&particles::BLOOD, e.x, e.y, 0.55, player.angle);
                             }
                         }
                     }
 
                     ws.weapon_cd = w.fire_interval;
+                    ws.bloom = (ws.bloom + w.bloom).min(w.bloom_max);
+                    match ws.mode {
+                        FireMode::Semi => ws.trigger_held = true,
+                        FireMode::Burst => {
+                            if ws.burst_left == 0 {
+                                ws.burst_left = w.burst;
+                                ws.trigger_held = true;
+                            }
+                            ws.burst_left -= 1;
+                            // dentro de la ráfaga las balas van más seguidas; al acabar, la pausa normal
+                            if ws.burst_left > 0 { ws.weapon_cd = w.burst_interval; }
+                        }
+                        FireMode::Auto => {}
+                    }
                 } else {
+                    ws.burst_left = 0;
                     // Auto-recarga si hay reserva
                     let pool = player.ammo_reserve[w.ammo_type.index()];
                     if pool > 0 && !ws.reloading {
@@ -1034,6 +1459,9 @@ if reload_pressed && !ws.reloading {
                     }
                 }
             }
+
+            // Los cohetes salen rectos: su mira no se abre
+            aim_cone = Some(if w.projectile { 0.0 } else { w.cone(ws.bloom, moving, player.crouch) });
         }
         // Devolver el slot
         player.slots[si] = Some(slot);
@@ -1046,42 +1474,22 @@ if reload_pressed && !ws.reloading {
 // Botón de ataque (igual que tus armas)
 
 
-// Si NO hay slot seleccionado (mano vacía = tecla 0) → puñetazo
-if attack_down && player.selected.is_none() && player.punch_cd <= 0.0 {
-    use crate::consts::{PUNCH_DAMAGE, PUNCH_RANGE, PUNCH_COOLDOWN};
-    let center = crate::consts::SCREEN_W / 2;
-
-    // Busca enemigo visible en el centro de pantalla y a rango corto
-    let mut best: Option<(usize, f32)> = None; // (idx_enemigo, profundidad)
-    for ds in &out.drawn {
-        if ds.start_x <= center && center <= ds.end_x && ds.depth <= PUNCH_RANGE {
-            // enemigo vivo
-            let e = &enemies[ds.idx];
-            if e.state != EnemyState::Dead && e.hp > 0 {
-                if best.map_or(true, |(_, d)| ds.depth < d) {
-                    best = Some((ds.idx, ds.depth));
-                }
-            }
-        }
-    }
-
-    if let Some((hit_idx, _)) = best {
-        // aplicar daño
-        if enemies[hit_idx].hp > 0 {
-            enemies[hit_idx].hp -= PUNCH_DAMAGE;
-            if enemies[hit_idx].hp <= 0 {
-                enemies[hit_idx].state = EnemyState::Dead;
-                kills += 1; // ✅ contamos el kill sólo al pasar a muerto
-                audio.play_enemy_death();
-                // si cuentas kills del jugador aquí:
-                // kills += 1;
-            } else {
-                enemies[hit_idx].flash_timer = 0.1; // feedback visual que ya usas
-                audio.play_enemy_hurt();
-            }
-        }
-        player.punch_cd = PUNCH_COOLDOWN;
-        // (opcional) feedback de pantalla/sonido
+// Cuerpo a cuerpo: el ataque normal con la mano vacía (puño) o con un cuchillo/bate en el slot;
+// el golpe rápido (V) sale con lo que haya en la mano (con un arma de fuego, culatazo = puño)
+let held_melee = match player.selected {
+    None => Some(MeleeType::Fist),
+    Some(si) => match player.slots[si] {
+        Some(SlotItem { item: Item::Melee(m), .. }) => Some(m),
+        _ => None,
+    },
+};
+let melee = held_melee.filter(|_| attack_down)
+    .or_else(|| quick_melee.then(|| held_melee.unwrap_or(MeleeType::Fist)));
+if let Some(m) = melee {
+    if player.melee_cd <= 0.0 {
+        let stats = m.stats();
+        kills += melee::swing(&stats, &map, &player, &mut enemies, &mut particles, &audio, &mut screen_fx);
+        player.melee_cd = stats.swing;
     }
 }
 
@@ -1089,10 +1497,7 @@ if attack_down && player.selected.is_none() && player.punch_cd <= 0.0 {
 
 
         // ---------------- HUD ----------------
-        d.draw_text(
-            "W/S=adelante/atrás | A/D=girar | ESPACIO=disparar | R=recargar | E=interactuar | 1–5=slot, 0=vacío | F=usar consumible",
-            10, 10, 18, Color::WHITE
-        );
+        d.draw_text(&bindings.help_text(HUD_HELP), 10, 10, 18, Color::WHITE);
 
 
         d.draw_text(&format!("Munición: "), 10, 58, 18, Color::YELLOW);
@@ -1109,20 +1514,26 @@ if attack_down && player.selected.is_none() && player.punch_cd <= 0.0 {
         // Barra de 5 slots
 
 
-        // Mira
-        let cx = SCREEN_W / 2; let cy = SCREEN_H / 2;
-        d.draw_line(cx - 8, cy, cx + 8, cy, Color::WHITE);
-        d.draw_line(cx, cy - 8, cx, cy + 8, Color::WHITE);
-
-        minimap.draw(&mut d, &map, player.x, player.y, player.angle);
+        minimap.draw(&mut d, vp, &map, player.x, player.y, player.angle);
         // enemigos vivos (ya lo calculabas para otro HUD)
 let enemies_left = enemies.iter().filter(|e| e.state != EnemyState::Dead && e.hp > 0).count();
 
 // stats bajo el minimapa
-hud::draw_top_right_stats(&mut d, &minimap, elapsed, enemies_left, kills);
+hud::draw_top_right_stats(&mut d, vp, &minimap, elapsed, enemies_left, kills, headshots);
 
-hud::draw_bottom_right_health_shield(&mut d, player.hp, player.shield, PLAYER_MAX_HP, PLAYER_MAX_SHIELD);
-hud::draw_slots_bar_bottom_left(&mut d, &player.slots, player.selected);
+hud::draw_bottom_right_health_shield(&mut d, vp, player.hp, player.shield, PLAYER_MAX_HP, PLAYER_MAX_SHIELD);
+hud::draw_stamina_bar_bottom_left(&mut d, vp, player.stamina, move_cfg.stamina_max);
+if !checkpoints.list.is_empty() {
+    hud::draw_lives_bottom_left(&mut d, vp, lives, PLAYER_LIVES);
+}
+if checkpoint_msg_t > 0.0 {
+    let txt = "CHECKPOINT";
+    let fs = 32;
+    let tw = d.measure_text(txt, fs);
+    let a = (checkpoint_msg_t.min(1.0) * 255.0) as u8;
+    d.draw_text(txt, (vp.w - tw) / 2, vp.h / 4, fs, Color::new(90, 230, 110, a));
+}
+hud::draw_slots_bar_bottom_left(&mut d, vp, &player.slots, player.selected);
 
 // Después de dibujar tu HUD habitual:
 // Cooldown de consumible: abajo al centro (estilo ammo HUD)
@@ -1137,32 +1548,80 @@ if let Some(si) = player.selected {
                     crate::types::ConsumableType::HealthBig   => CD_HEALTH_BIG,
                     crate::types::ConsumableType::ShieldSmall => CD_SHIELD_SMALL,
                     crate::types::ConsumableType::ShieldBig   => CD_SHIELD_BIG,
+                    crate::types::ConsumableType::Grenade     => 0.0, // nunca se canaliza
                 };
 
                 // Nuevo: dibuja centrado abajo
-                hud::draw_consumable_cooldown_center_bottom(&mut d, slot.cd, total, kind);
+                hud::draw_consumable_cooldown_center_bottom(&mut d, vp, slot.cd, total, kind);
             }
         }
     }
 }
+hud::draw_grenade_charge_center_bottom(&mut d, vp, player.grenade_charge);
 
 
 
+// Mira: el cono del arma pasado a píxeles de la ventana (la vista interna se escala a toda la ventana)
+if let Some(cone) = aim_cone {
+    if death_t <= 0.0 {
+        let spread_px = cone.tan() * proj_dist * vp.w as f32 / view_w as f32;
+        hud::draw_crosshair(&mut d, vp, spread_px);
+    }
+}
+
 // Si el slot seleccionado es arma, mostrar arma empuñada (PNG) + balas (mag/reserva)
 if let Some(si) = player.selected {
     if let Some(slot) = &player.slots[si] {
+        // Cuchillo / bate: sólo la textura, barriendo mientras dura el golpe
+        if let Item::Melee(m) = slot.item {
+            let anim = hud::HeldWeaponAnim {
+                bob_t: player.bob_t,
+                bob_amp: player.bob_amp,
+                kick: 0.0,
+                reload: None,
+                draw: 0.0,
+                spin: 0.0,
+                swing: player.melee_cd / m.stats().swing,
+            };
+            if let Some(tex) = weapon_hud_tex.melee_tex(m) {
+                hud::draw_held_weapon_center_bottom(&mut d, vp, tex, &anim);
+            }
+        }
         if let Item::Weapon(w, ws) = &slot.item {
             // 1) Arma empuñada (PNG) centrada abajo, justo encima del HUD de balas
-            let tex = weapon_hud_tex.tex(w.wtype);   // ← instancia creada fuera del loop
-            hud::draw_held_weapon_center_bottom(&mut d, tex);
+            let anim = hud::HeldWeaponAnim {
+                bob_t: player.bob_t,
+                bob_amp: player.bob_amp,
+                kick: ws.kick,
+                reload: ws.reloading.then(|| 1.0 - ws.reload_cd / w.effective_reload().max(1e-3)),
+                draw: player.draw_t / w.draw_time.max(1e-3),
+                spin: ws.spin,
+                swing: player.melee_cd / MeleeType::Fist.stats().swing, // culatazo (golpe rápido)
+            };
+            if let Some(tex) = weapon_hud_tex.tex(w.wtype) {   // ← instancia creada fuera del loop
+                hud::draw_held_weapon_center_bottom(&mut d, vp, tex, &anim);
+            }
 
             // 2) Contador de balas (mag / reserva)
             let reserve = player.ammo_reserve[w.ammo_type.index()] as i32;
-            hud::draw_ammo_center_bottom(&mut d, ws.ammo_in_mag as i32, reserve);
+            hud::draw_ammo_center_bottom(&mut d, vp, ws.ammo_in_mag as i32, reserve, ws.mode.label(), w.fire_modes.len() > 1);
         }
     }
 }
 
+// Efectos de pantalla (encima de todo el HUD)
+screen_fx.draw(&mut d, vp, player.angle);
+
+// Muerto: la pantalla se va tiñendo de rojo oscuro mientras cae la cámara
+if death_t > 0.0 {
+    let a = (death_t / DEATH_CAM_TIME).min(1.0);
+    d.draw_rectangle(0, 0, vp.w, vp.h, Color::new(90, 0, 0, (a * 170.0) as u8));
+    let txt = "HAS MUERTO";
+    let fs = 56;
+    let tw = d.measure_text(txt, fs);
+    d.draw_text(txt, (vp.w - tw) / 2, vp.h / 3, fs, Color::new(255, 255, 255, (a * 255.0) as u8));
+}
+
 
 
     }
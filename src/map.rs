@@ -1,6 +1,66 @@
 use std::fs;
 use std::path::Path;
 
+use crate::consts::{SEE_THROUGH_WALLS, WALL_WINDOW};
+
+/// Qué deja en el mapa cada letra de spawn (la celda queda como suelo)
+#[derive(Clone, Copy)]
+enum Spawn {
+    Player,
+    Enemy,
+    HealthBig,
+    ShieldBig,
+    DecoBlock,
+    DecoGhost,
+    Chest,
+    AmmoLight,
+    AmmoMedium,
+    AmmoHeavy,
+    AmmoShell,
+    AmmoRocket,
+    HealRandom,
+    ShieldRandom,
+    WeaponRandom,
+    AmmoRandom,
+    Knife,
+    Bat,
+    Grenade,
+    Checkpoint,
+}
+
+/// Letras de spawn fijas de `from_txt`
+const SPAWN_GLYPHS: &[(char, Spawn)] = &[
+    ('P', Spawn::Player), ('E', Spawn::Enemy),
+    ('H', Spawn::HealthBig), ('S', Spawn::ShieldBig),
+    ('B', Spawn::DecoBlock), ('b', Spawn::DecoGhost), ('C', Spawn::Chest),
+    ('t', Spawn::AmmoLight), ('y', Spawn::AmmoMedium), ('u', Spawn::AmmoHeavy),
+    ('g', Spawn::AmmoShell), ('r', Spawn::AmmoRocket),
+    ('h', Spawn::HealRandom), ('s', Spawn::ShieldRandom),
+    ('w', Spawn::WeaponRandom), ('m', Spawn::AmmoRandom),
+    ('N', Spawn::Knife), ('T', Spawn::Bat), ('G', Spawn::Grenade),
+    ('X', Spawn::Checkpoint),
+];
+
+/// Pared o suelo según la letra (None = no es ninguna de las dos)
+fn tile_of(ch: char) -> Option<u8> {
+    match ch {
+        '1'..='9' => Some(ch as u8 - b'0'), // paredes 1..9
+        '#' => Some(1),                     // compat: muro básico
+        'V' => Some(WALL_WINDOW),           // ventana (see-through)
+        '0' | ' ' | '.' => Some(0),         // suelo
+        _ => None,
+    }
+}
+
+fn spawn_of(ch: char) -> Option<Spawn> {
+    SPAWN_GLYPHS.iter().find(|&&(c, _)| c == ch).map(|&(_, s)| s)
+}
+
+/// ¿La letra ya significa algo en el mapa? (el catálogo de armas las rechaza como `glyph`)
+pub fn is_reserved_glyph(ch: char) -> bool {
+    tile_of(ch).is_some() || spawn_of(ch).is_some()
+}
+
 pub struct Map {
     pub w: usize,
     pub h: usize,
@@ -28,12 +88,15 @@ pub struct Map {
     pub ammo_shell_spawns:  Vec<(f32, f32)>,
     pub ammo_rocket_spawns: Vec<(f32, f32)>,
 
-    // 🔹 NUEVO: spawns de armas
-    pub weapon_pistol_spawns: Vec<(f32, f32)>, // A
-    pub weapon_smg_spawns:    Vec<(f32, f32)>, // M
-    pub weapon_rifle_spawns:  Vec<(f32, f32)>, // R
-    pub weapon_shotgun_spawns:Vec<(f32, f32)>, // O
-    pub weapon_rocket_spawns: Vec<(f32, f32)>, // K
+    // cuerpo a cuerpo: 'N' = cuchillo (navaja), 'T' = bate
+    pub knife_spawns: Vec<(f32, f32)>,
+    pub bat_spawns:   Vec<(f32, f32)>,
+
+    // 'G' = granadas (un pack de GRENADE_PACK)
+    pub grenade_spawns: Vec<(f32, f32)>,
+
+    // 🔹 letras sin significado fijo (p. ej. armas del catálogo con `glyph`): (letra, x, y)
+    pub glyph_spawns: Vec<(char, f32, f32)>,
 
     pub heal_random_spawns: Vec<(f32, f32)>,   // lugares donde puede salir vida pequeña / vida mayor / nada
     pub shield_random_spawns: Vec<(f32, f32)>, // lugares donde puede salir escudo menor / escudo mayor / nada
@@ -41,6 +104,13 @@ pub struct Map {
 
     pub ammo_random_spawns: Vec<(f32, f32)>,
 
+    // 🚩 checkpoints ('X'): al tocarlos se guarda el estado de la partida
+    pub checkpoint_spawns: Vec<(f32, f32)>,
+
+    // 🌄 cielo: panorama del mapa (`@sky <ruta>`) y celdas abiertas al cielo (capa `@ceiling`)
+    pub sky_texture: Option<String>,
+    sky: Vec<bool>,
+
 }
 
 
@@ -48,7 +118,30 @@ pub struct Map {
 impl Map {
     pub fn from_txt<P: AsRef<Path>>(path: P) -> Result<Self, String> {
         let txt = fs::read_to_string(&path).map_err(|e| format!("No se pudo leer el mapa: {e}"))?;
-        let lines: Vec<&str> = txt.lines().filter(|l| !l.trim().is_empty()).collect();
+        // Directivas (líneas que empiezan con '@'):
+        //   @sky <ruta>   → panorama del cielo para este mapa
+        //   @ceiling      → las líneas siguientes son la capa de techo ('#' = techado, otro = cielo abierto)
+        let mut lines: Vec<&str> = Vec::new();
+        let mut ceiling_lines: Vec<&str> = Vec::new();
+        let mut sky_texture: Option<String> = None;
+        let mut in_ceiling = false;
+        for l in txt.lines().filter(|l| !l.trim().is_empty()) {
+            let t = l.trim();
+            if let Some(dir) = t.strip_prefix('@') {
+                let mut parts = dir.splitn(2, char::is_whitespace);
+                match parts.next().unwrap_or("") {
+                    "sky" => {
+                        let path = parts.next().map(str::trim).unwrap_or("");
+                        if path.is_empty() { return Err("Directiva @sky sin ruta".into()); }
+                        sky_texture = Some(path.to_string());
+                    }
+                    "ceiling" => in_ceiling = true,
+                    other => return Err(format!("Directiva de mapa desconocida: @{other}")),
+                }
+                continue;
+            }
+            if in_ceiling { ceiling_lines.push(l); } else { lines.push(l); }
+        }
         if lines.is_empty() { return Err("El archivo de mapa está vacío".into()); }
 
         let w = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
@@ -72,11 +165,13 @@ impl Map {
         let mut ammo_shell_spawns  = Vec::new();
         let mut ammo_rocket_spawns = Vec::new();
 
-        let mut weapon_pistol_spawns = Vec::new();
-        let mut weapon_smg_spawns    = Vec::new();
-        let mut weapon_rifle_spawns  = Vec::new();
-        let mut weapon_shotgun_spawns= Vec::new();
-        let mut weapon_rocket_spawns = Vec::new();
+
+        let mut knife_spawns = Vec::new();
+        let mut bat_spawns   = Vec::new();
+
+        let mut grenade_spawns = Vec::new();
+
+        let mut glyph_spawns = Vec::new();
 
         let mut heal_random_spawns = Vec::new();
         let mut shield_random_spawns = Vec::new();
@@ -85,89 +180,80 @@ impl Map {
 
         let mut ammo_random_spawns = Vec::new();
 
+        let mut checkpoint_spawns = Vec::new();
+
         for (y, raw) in lines.iter().enumerate() {
             let mut row: Vec<char> = raw.chars().collect();
             if row.len() < w { row.resize(w, '1'); }
             for (x, ch) in row.into_iter().enumerate() {
-                match ch {
-                        // PAREDES
-    '1'..='9' => {
-        let id = (ch as u8 - b'0') as u8; // 1..9
-        data.push(id);
-    }
-    '#' => { data.push(1); } // compat: muro básico
-
-    // SUELO
-    '0' | ' ' | '.' => data.push(0),
-
-    // Player / Enemigos
-    'P' => { data.push(0); if player_spawn.is_none() { player_spawn = Some((x as f32 + 0.5, y as f32 + 0.5)); } }
-    'E' => { data.push(0); enemy_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-
-                    // 🔹 nuevos pickups:
-                    'H' => { data.push(0); health_big_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'S' => { data.push(0); shield_big_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'B' => { data.push(0); deco_block_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'b' => { data.push(0); deco_ghost_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'C' => { data.push(0); chest_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
+                if let Some(id) = tile_of(ch) {
+                    data.push(id);
+                    continue;
+                }
+                data.push(0); // el resto es suelo con algo encima
+                let pos = (x as f32 + 0.5, y as f32 + 0.5);
+                match spawn_of(ch) {
+                    Some(Spawn::Player) => { if player_spawn.is_none() { player_spawn = Some(pos); } }
+                    Some(Spawn::Enemy) => enemy_spawns.push(pos),
+
+                    // 🔹 pickups, decoraciones y cofres
+                    Some(Spawn::HealthBig) => health_big_spawns.push(pos),
+                    Some(Spawn::ShieldBig) => shield_big_spawns.push(pos),
+                    Some(Spawn::DecoBlock) => deco_block_spawns.push(pos),
+                    Some(Spawn::DecoGhost) => deco_ghost_spawns.push(pos),
+                    Some(Spawn::Chest) => chest_spawns.push(pos),
 
                     // 🔹 munición
-                    't' => { data.push(0); ammo_light_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'y' => { data.push(0); ammo_medium_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'u' => { data.push(0); ammo_heavy_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'g' => { data.push(0); ammo_shell_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'r' => { data.push(0); ammo_rocket_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-
-
-                    'A' => { data.push(0); weapon_pistol_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'M' => { data.push(0); weapon_smg_spawns.push(   (x as f32 + 0.5, y as f32 + 0.5)); }
-                    'R' => { data.push(0); weapon_rifle_spawns.push( (x as f32 + 0.5, y as f32 + 0.5)); }
-                    'O' => { data.push(0); weapon_shotgun_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-                    'K' => { data.push(0); weapon_rocket_spawns.push((x as f32 + 0.5, y as f32 + 0.5)); }
-
-                    'h' => {
-                        // spawn aleatorio de vida
-                        data.push(0); // ← suelo (antes intentabas usar cells[idx])
-                        heal_random_spawns.push((x as f32 + 0.5, y as f32 + 0.5));
-                    }
-                    's' => {
-                        // spawn aleatorio de escudo
-                        data.push(0); // ← suelo
-                        shield_random_spawns.push((x as f32 + 0.5, y as f32 + 0.5));
-                    }
-
-                    'w' => {
-                        data.push(0); // suelo
-                        weapon_random_spawns.push((x as f32 + 0.5, y as f32 + 0.5));
+                    Some(Spawn::AmmoLight) => ammo_light_spawns.push(pos),
+                    Some(Spawn::AmmoMedium) => ammo_medium_spawns.push(pos),
+                    Some(Spawn::AmmoHeavy) => ammo_heavy_spawns.push(pos),
+                    Some(Spawn::AmmoShell) => ammo_shell_spawns.push(pos),
+                    Some(Spawn::AmmoRocket) => ammo_rocket_spawns.push(pos),
+
+                    // spawns aleatorios (vida / escudo / arma / munición o nada)
+                    Some(Spawn::HealRandom) => heal_random_spawns.push(pos),
+                    Some(Spawn::ShieldRandom) => shield_random_spawns.push(pos),
+                    Some(Spawn::WeaponRandom) => weapon_random_spawns.push(pos),
+                    Some(Spawn::AmmoRandom) => ammo_random_spawns.push(pos),
+
+                    // cuerpo a cuerpo
+                    Some(Spawn::Knife) => knife_spawns.push(pos),
+                    Some(Spawn::Bat) => bat_spawns.push(pos),
+                    Some(Spawn::Grenade) => grenade_spawns.push(pos),
+
+                    Some(Spawn::Checkpoint) => checkpoint_spawns.push(pos),
+
+                    // cualquier otra letra es suelo; se anota por si significa algo fuera del mapa
+                    None => {
+                        if ch.is_alphanumeric() { glyph_spawns.push((ch, pos.0, pos.1)); }
                     }
-
-                    'm' => {
-    data.push(0);
-    ammo_random_spawns.push((x as f32 + 0.5, y as f32 + 0.5));
-}
-
-
-
-                    _ => data.push(0),
                 }
             }
         }
 
+        // Sin capa @ceiling todo el mapa queda abierto al cielo
+        let mut sky = vec![true; w * h];
+        for (y, raw) in ceiling_lines.iter().take(h).enumerate() {
+            for (x, ch) in raw.chars().take(w).enumerate() {
+                sky[y * w + x] = ch != '#';
+            }
+        }
+
         Ok(Self {
             w, h, data,
+            sky_texture, sky,
             player_spawn, enemy_spawns,
             health_small_spawns, health_big_spawns, shield_small_spawns, shield_big_spawns,
             deco_block_spawns, deco_ghost_spawns,
             chest_spawns,
             ammo_light_spawns, ammo_medium_spawns, ammo_heavy_spawns, ammo_shell_spawns, ammo_rocket_spawns,
-            weapon_pistol_spawns,
-    weapon_smg_spawns,
-    weapon_rifle_spawns,
-    weapon_shotgun_spawns,
-    weapon_rocket_spawns,
+            knife_spawns, bat_spawns,
+            grenade_spawns,
+            glyph_spawns,
     heal_random_spawns,
     shield_random_spawns,
     weapon_random_spawns, ammo_random_spawns,
+    checkpoint_spawns,
         })
     }
 
@@ -178,6 +264,25 @@ impl Map {
     }
 
 
+    /// ¿Se ve el cielo sobre esta celda? (fuera del mapa → sí)
+    #[inline]
+    pub fn open_to_sky(&self, x: i32, y: i32) -> bool {
+        if x < 0 || y < 0 || x as usize >= self.w || y as usize >= self.h { true }
+        else { self.sky[y as usize * self.w + x as usize] }
+    }
+
+    /// ¿La celda es una pared con huecos (rejilla/valla/ventana)?
+    #[inline]
+    pub fn is_see_through(&self, x: i32, y: i32) -> bool {
+        SEE_THROUGH_WALLS.contains(&self.at(x, y))
+    }
+
+    /// ¿La celda corta la línea de visión / las balas? (paredes opacas)
+    #[inline]
+    pub fn blocks_sight(&self, x: i32, y: i32) -> bool {
+        self.at(x, y) > 0 && !self.is_see_through(x, y)
+    }
+
     pub fn is_solid(&self, x: i32, y: i32) -> bool {
     self.at(x, y) > 0 // cualquier id > 0 es pared
 }
@@ -0,0 +1,59 @@
+use crate::map::Map;
+use crate::types::{Player, Enemy, EnemyState, MeleeStats};
+use crate::ai::has_los;
+use crate::audio::Audio;
+use crate::particles::{self, Particles};
+use crate::screen_fx::ScreenFx;
+use crate::consts::ENEMY_RADIUS;
+
+/// Golpe cuerpo a cuerpo: da a **todos** los enemigos vivos dentro del arco
+/// delante del jugador (posiciones del mundo, no columnas de pantalla).
+/// Un enemigo cuenta si su borde cae dentro del alcance y del arco, y no hay pared en medio.
+/// Devuelve los kills.
+pub fn swing(
+    stats: &MeleeStats,
+    map: &Map,
+    player: &Player,
+    enemies: &mut [Enemy],
+    particles: &mut Particles,
+    audio: &Audio,
+    fx: &mut ScreenFx,
+) -> u32 {
+    audio.play_swing();
+
+    let mut kills = 0;
+    let mut hit_any = false;
+    for e in enemies.iter_mut() {
+        if !e.is_alive() { continue; }
+        let (dx, dy) = (e.x - player.x, e.y - player.y);
+        let d = dx.hypot(dy);
+        if d - ENEMY_RADIUS > stats.range { continue; }
+
+        // ángulo respecto a la mirada, ensanchado por lo que ocupa el enemigo
+        let to_enemy = dy.atan2(dx);
+        let off = (to_enemy - player.angle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
+        let half = stats.arc / 2.0 + (ENEMY_RADIUS / d.max(1e-3)).atan();
+        if off.abs() > half || !has_los(map, player.x, player.y, e.x, e.y) { continue; }
+
+        hit_any = true;
+        e.hp -= stats.damage;
+        if d > 1e-3 {
+            e.knock.0 += dx / d * stats.knockback;
+            e.knock.1 += dy / d * stats.knockback;
+        }
+        if e.hp <= 0 {
+            e.state = EnemyState::Dead;
+            kills += 1; // ✅ contamos el kill sólo al pasar a muerto
+            audio.play_enemy_death();
+            particles.emit(&particles::GIBS, e.x, e.y, 0.45, to_enemy);
+        } else {
+            e.flash_timer = 0.1;
+            audio.play_enemy_hurt();
+            particles.emit(&particles::BLOOD, e.x, e.y, 0.55, to_enemy);
+        }
+    }
+    if hit_any {
+        fx.on_hit(false);
+    }
+    kills
+}
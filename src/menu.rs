@@ -1,11 +1,21 @@
 use raylib::prelude::*;
-use crate::consts::{SCREEN_W, SCREEN_H};
 use raylib::consts::MouseButton;
+use crate::types::Viewport;
+use crate::input::{Action, Binding, Bindings, MENU_HELP, CONTROLS_HELP};
+use crate::consts::CONTROLS_PATH;
 
 /// Acciones posibles del menú
 pub enum MenuAction {
     None,
     Start,
+    Quit, // MenuBack en la pantalla principal
+}
+
+/// Pantalla activa dentro del menú
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MenuScreen {
+    Main,
+    Controls,
 }
 
 pub struct Menu {
@@ -13,6 +23,13 @@ pub struct Menu {
     btn_text: String,
     btn_size: (i32, i32),
     hovered: bool,
+    hovered_controls: bool,
+    focus: usize, // botón con foco para teclado/mando: 0 = iniciar, 1 = controles
+    // pantalla de controles
+    screen: MenuScreen,
+    sel: usize,       // fila seleccionada (índice en Action::game())
+    waiting: bool,    // esperando la nueva tecla para `sel`
+    hovered_back: bool,
 }
 
 impl Menu {
@@ -22,71 +39,254 @@ impl Menu {
             btn_text: "INICIAR".to_string(),
             btn_size: (260, 64),
             hovered: false,
+            hovered_controls: false,
+            focus: 0,
+            screen: MenuScreen::Main,
+            sel: 0,
+            waiting: false,
+            hovered_back: false,
         }
     }
 
     /// Procesa entrada del usuario (teclado/ratón). Llama **antes** de begin_drawing.
-    pub fn handle_input(&mut self, rl: &RaylibHandle) -> MenuAction {
-        let (bx, by, bw, bh) = self.button_rect();
+    pub fn handle_input(&mut self, rl: &mut RaylibHandle, bindings: &mut Bindings) -> MenuAction {
+        if self.screen == MenuScreen::Controls {
+            self.handle_controls_input(rl, bindings);
+            return MenuAction::None;
+        }
+
+        let vp = Viewport::of(rl);
         let m = rl.get_mouse_position();
+        let inside = |(x, y, w, h): (i32, i32, i32, i32)| {
+            m.x >= x as f32 && m.x <= (x + w) as f32 && m.y >= y as f32 && m.y <= (y + h) as f32
+        };
 
-        self.hovered = m.x >= bx as f32 && m.x <= (bx + bw) as f32 &&
-                       m.y >= by as f32 && m.y <= (by + bh) as f32;
+        self.hovered = inside(self.button_rect(vp));
+        self.hovered_controls = inside(self.controls_button_rect(vp));
+        if self.hovered { self.focus = 0; }
+        if self.hovered_controls { self.focus = 1; }
 
-        if self.hovered && rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
-            return MenuAction::Start;
+        if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
+            if self.hovered { return MenuAction::Start; }
+            if self.hovered_controls { self.open_controls(); return MenuAction::None; }
+        }
+        if bindings.pressed(rl, Action::MenuControls) {
+            self.open_controls();
+            return MenuAction::None;
+        }
+        if bindings.pressed(rl, Action::MenuBack) {
+            return MenuAction::Quit;
         }
-        if rl.is_key_pressed(KeyboardKey::KEY_ENTER) || rl.is_key_pressed(KeyboardKey::KEY_SPACE) {
+
+        // Teclado/mando: ↑/↓ (cruceta o stick) mueven el foco, aceptar lo activa
+        if bindings.pressed(rl, Action::MenuUp)   { self.focus = 0; }
+        if bindings.pressed(rl, Action::MenuDown) { self.focus = 1; }
+        if bindings.pressed(rl, Action::MenuConfirm) {
+            if self.focus == 1 {
+                self.open_controls();
+                return MenuAction::None;
+            }
             return MenuAction::Start;
         }
         MenuAction::None
     }
 
+    fn open_controls(&mut self) {
+        self.screen = MenuScreen::Controls;
+        self.waiting = false;
+        self.sel = 0;
+    }
+
+    fn close_controls(&mut self, bindings: &Bindings) {
+        self.screen = MenuScreen::Main;
+        self.waiting = false;
+        if let Err(e) = bindings.save(CONTROLS_PATH) {
+            eprintln!("{e}");
+        }
+    }
+
+    /// Pantalla de controles: MenuUp/MenuDown o mouse eligen, MenuConfirm/click reasigna,
+    /// MenuClear vuelve al de fábrica, MenuBack o VOLVER guardan y salen.
+    fn handle_controls_input(&mut self, rl: &mut RaylibHandle, bindings: &mut Bindings) {
+        let actions = Action::game();
+        let action = actions[self.sel];
+
+        if self.waiting {
+            // La primera tecla con nombre, botón del mouse o del mando queda asignada; ESC cancela
+            while let Some(key) = rl.get_key_pressed() {
+                if key == KeyboardKey::KEY_ESCAPE {
+                    self.waiting = false;
+                    return;
+                }
+                if let Some(b) = Binding::from_key(key) {
+                    bindings.rebind(action, b);
+                    self.waiting = false;
+                    return;
+                }
+            }
+            if let Some(b) = Binding::mouse_pressed(rl).or_else(|| bindings.pad_pressed(rl)) {
+                bindings.rebind(action, b);
+                self.waiting = false;
+            }
+            return;
+        }
+
+        let vp = Viewport::of(rl);
+        let m = rl.get_mouse_position();
+        let md = rl.get_mouse_delta();
+
+        // Fila bajo el mouse (sólo si el mouse se movió, para no pelear con ↑/↓)
+        let hovered_row = (0..actions.len()).find(|&i| {
+            let (x, y, w, h) = self.row_rect(vp, i);
+            m.x >= x as f32 && m.x <= (x + w) as f32 && m.y >= y as f32 && m.y < (y + h) as f32
+        });
+        if md.x != 0.0 || md.y != 0.0 {
+            if let Some(i) = hovered_row { self.sel = i; }
+        }
+
+        let (bx, by, bw, bh) = self.back_button_rect(vp);
+        self.hovered_back = m.x >= bx as f32 && m.x <= (bx + bw) as f32 &&
+                            m.y >= by as f32 && m.y <= (by + bh) as f32;
+
+        let n = actions.len();
+        if bindings.pressed(rl, Action::MenuDown) { self.sel = (self.sel + 1) % n; }
+        if bindings.pressed(rl, Action::MenuUp)   { self.sel = (self.sel + n - 1) % n; }
+        if bindings.pressed(rl, Action::MenuClear) { bindings.reset(actions[self.sel]); }
+
+        if bindings.pressed(rl, Action::MenuConfirm) {
+            self.waiting = true;
+            return;
+        }
+        if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
+            if self.hovered_back {
+                self.close_controls(bindings);
+            } else if let Some(i) = hovered_row {
+                self.sel = i;
+                self.waiting = true;
+            }
+            return;
+        }
+        if bindings.pressed(rl, Action::MenuBack) {
+            self.close_controls(bindings);
+        }
+    }
+
     /// Dibuja la pantalla de inicio. Llama dentro del begin_drawing.
-    pub fn draw(&self, d: &mut RaylibDrawHandle) {
+    pub fn draw(&self, d: &mut RaylibDrawHandle, bindings: &Bindings) {
+        let vp = Viewport::of(d);
+        let (sw, sh) = (vp.w, vp.h);
+
         // Fondo
         d.draw_rectangle_gradient_v(
-            0, 0, SCREEN_W, SCREEN_H,
+            0, 0, sw, sh,
             Color::new(15, 15, 30, 255),
             Color::new(35, 35, 70, 255),
         );
 
+        if self.screen == MenuScreen::Controls {
+            self.draw_controls(d, vp, bindings);
+            return;
+        }
+
         // Título
         let title_fs = 48;
         let tw = d.measure_text(&self.title, title_fs);
-        d.draw_text(&self.title, (SCREEN_W - tw)/2 + 2, 90 + 2, title_fs, Color::new(0,0,0,150));
-        d.draw_text(&self.title, (SCREEN_W - tw)/2,      90,     title_fs, Color::WHITE);
+        d.draw_text(&self.title, (sw - tw)/2 + 2, 90 + 2, title_fs, Color::new(0,0,0,150));
+        d.draw_text(&self.title, (sw - tw)/2,      90,     title_fs, Color::WHITE);
 
-        // Subtítulo / tips
-        let sub = "W/S: avanzar | A/D: girar | 1..5/0: slots | M: minimapa";
+        // Subtítulo / tips (según los controles activos)
+        let sub = bindings.help_text(MENU_HELP);
         let sub_fs = 20;
-        let sw = d.measure_text(sub, sub_fs);
-        d.draw_text(sub, (SCREEN_W - sw)/2, 150, sub_fs, Color::new(220,220,220,230));
+        let subw = d.measure_text(&sub, sub_fs);
+        d.draw_text(&sub, (sw - subw)/2, 150, sub_fs, Color::new(220,220,220,230));
 
         // Botón
-        let (bx, by, bw, bh) = self.button_rect();
+        let (bx, by, bw, bh) = self.button_rect(vp);
+        self.draw_button(d, (bx, by, bw, bh), &self.btn_text, 28, self.focus == 0);
+
+        // Hint (según los controles activos)
+        let hint = format!("{} (o click)", bindings.help_text(&[(&[Action::MenuConfirm], "iniciar")]));
+        let hf = 18;
+        let hw = d.measure_text(&hint, hf);
+        d.draw_text(&hint, (sw - hw)/2, by + bh + 14, hf, Color::new(230,230,230,220));
+
+        let controls_txt = format!("CONTROLES ({})", bindings.label(Action::MenuControls));
+        self.draw_button(d, self.controls_button_rect(vp), &controls_txt, 20, self.focus == 1);
+    }
+
+    fn draw_controls(&self, d: &mut RaylibDrawHandle, vp: Viewport, bindings: &Bindings) {
+        let sw = vp.w;
+
+        let title = "CONTROLES";
+        let title_fs = 36;
+        let tw = d.measure_text(title, title_fs);
+        d.draw_text(title, (sw - tw)/2 + 2, 24 + 2, title_fs, Color::new(0,0,0,150));
+        d.draw_text(title, (sw - tw)/2,     24,     title_fs, Color::WHITE);
+
+        for (i, &a) in Action::game().iter().enumerate() {
+            let (x, y, w, h) = self.row_rect(vp, i);
+            let selected = i == self.sel;
+            if selected {
+                let bg = if self.waiting { Color::new(200,150,40,200) } else { Color::new(70,130,250,160) };
+                d.draw_rectangle(x, y, w, h, bg);
+            }
+
+            let fs = (h - 4).clamp(10, 18);
+            let ty = y + (h - fs) / 2;
+            d.draw_text(a.label(), x + 10, ty, fs, Color::WHITE);
+
+            let keys = if selected && self.waiting { "pulsa una tecla…".to_string() } else { bindings.label(a) };
+            let kw = d.measure_text(&keys, fs);
+            d.draw_text(&keys, x + w - kw - 10, ty, fs, Color::new(255,230,120,255));
+        }
+
+        let (bx, by, bw, bh) = self.back_button_rect(vp);
+        self.draw_button(d, (bx, by, bw, bh), "VOLVER", 22, self.hovered_back);
+
+        let hint = format!("{} | click=reasignar", bindings.help_text(CONTROLS_HELP));
+        let hf = 16;
+        let hw = d.measure_text(&hint, hf);
+        d.draw_text(&hint, (sw - hw)/2, by - hf - 8, hf, Color::new(230,230,230,220));
+    }
+
+    fn draw_button(&self, d: &mut RaylibDrawHandle, rect: (i32, i32, i32, i32), label: &str, fs: i32, hovered: bool) {
+        let (bx, by, bw, bh) = rect;
         let round = 0.5;
         let segs = 12;
-        let bg = if self.hovered { Color::new(70,130,250,255) } else { Color::new(50,90,200,255) };
+        let bg = if hovered { Color::new(70,130,250,255) } else { Color::new(50,90,200,255) };
         d.draw_rectangle_rounded(Rectangle{ x: bx as f32, y: by as f32, width: bw as f32, height: bh as f32 }, round, segs, bg);
         d.draw_rectangle_rounded_lines(Rectangle{ x: bx as f32, y: by as f32, width: bw as f32, height: bh as f32 }, round, segs, Color::WHITE);
 
-        let fs = 28;
-        let btw = d.measure_text(&self.btn_text, fs);
-        d.draw_text(&self.btn_text, bx + (bw - btw)/2, by + (bh - fs)/2, fs, Color::WHITE);
-
-        // Hint
-        let hint = "ENTER o CLICK para iniciar";
-        let hf = 18;
-        let hw = d.measure_text(hint, hf);
-        d.draw_text(hint, (SCREEN_W - hw)/2, by + bh + 14, hf, Color::new(230,230,230,220));
+        let btw = d.measure_text(label, fs);
+        d.draw_text(label, bx + (bw - btw)/2, by + (bh - fs)/2, fs, Color::WHITE);
     }
 
     /// Rectángulo del botón centrado en la parte inferior media.
-    fn button_rect(&self) -> (i32, i32, i32, i32) {
+    fn button_rect(&self, vp: Viewport) -> (i32, i32, i32, i32) {
         let (bw, bh) = self.btn_size;
-        let x = (SCREEN_W - bw) / 2;
-        let y = (SCREEN_H * 2 / 3) - (bh / 2);
+        let x = (vp.w - bw) / 2;
+        let y = (vp.h * 2 / 3) - (bh / 2);
         (x, y, bw, bh)
     }
+
+    /// Botón de controles, debajo del hint de iniciar
+    fn controls_button_rect(&self, vp: Viewport) -> (i32, i32, i32, i32) {
+        let (_, by, _, bh) = self.button_rect(vp);
+        let (w, h) = (240, 44);
+        ((vp.w - w) / 2, by + bh + 46, w, h)
+    }
+
+    /// Fila `i` de la lista de controles: reparte el alto disponible entre todas
+    fn row_rect(&self, vp: Viewport, i: usize) -> (i32, i32, i32, i32) {
+        let top = 76;
+        let bottom = vp.h - 110;
+        let h = ((bottom - top) / Action::game().len() as i32).min(26);
+        let w = 560.min(vp.w - 40);
+        ((vp.w - w) / 2, top + i as i32 * h, w, h)
+    }
+
+    fn back_button_rect(&self, vp: Viewport) -> (i32, i32, i32, i32) {
+        let (w, h) = (200, 44);
+        ((vp.w - w) / 2, vp.h - h - 20, w, h)
+    }
 }
@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+use raylib::prelude::Color;
+use rand::Rng;
+use rand::rngs::ThreadRng;
+
+use crate::consts::PARTICLE_MAX;
+use crate::map::Map;
+
+/// Receta de un emisor: cuántas partículas salen, cómo salen y cómo envejecen.
+/// Las curvas de color/tamaño van de `*_start` a `*_end` a lo largo de la vida.
+/// Unidades: x/y en celdas, z en alturas de pared (0 = suelo, 0.5 = ojos, 1 = techo).
+pub struct Emitter {
+    pub count: u32,
+    pub life: (f32, f32),  // vida (s), mín..máx
+    pub speed: (f32, f32), // velocidad horizontal (celdas/s)
+    pub up: (f32, f32),    // velocidad vertical inicial (alturas/s)
+    pub spread: f32,       // apertura (rad) alrededor de la dirección; TAU = en todas direcciones
+    pub gravity: f32,      // alturas/s² hacia abajo (negativa = flota hacia arriba)
+    pub color_start: Color,
+    pub color_end: Color,
+    pub size_start: f32,   // tamaño en mundo (1.0 = alto de pared)
+    pub size_end: f32,
+}
+
+// ── Presets ──────────────────────────────────────────────
+pub const BLOOD: Emitter = Emitter {
+    count: 10, life: (0.35, 0.7), speed: (0.6, 1.6), up: (0.2, 1.0), spread: 1.4, gravity: 3.0,
+    color_start: Color::new(200, 20, 20, 255), color_end: Color::new(90, 0, 0, 0),
+    size_start: 0.035, size_end: 0.02,
+};
+pub const GIBS: Emitter = Emitter {
+    count: 26, life: (0.6, 1.2), speed: (0.4, 2.2), up: (0.5, 1.8), spread: std::f32::consts::TAU, gravity: 3.5,
+    color_start: Color::new(170, 10, 10, 255), color_end: Color::new(60, 0, 0, 0),
+    size_start: 0.05, size_end: 0.025,
+};
+pub const WALL_DUST: Emitter = Emitter {
+    count: 8, life: (0.25, 0.5), speed: (0.3, 1.0), up: (0.0, 0.6), spread: 1.6, gravity: 1.5,
+    color_start: Color::new(200, 190, 170, 230), color_end: Color::new(120, 110, 100, 0),
+    size_start: 0.02, size_end: 0.04,
+};
+pub const HEAL_SPARKLE: Emitter = Emitter {
+    count: 18, life: (0.6, 1.1), speed: (0.05, 0.3), up: (0.3, 0.6), spread: std::f32::consts::TAU, gravity: -0.2,
+    color_start: Color::new(120, 255, 120, 255), color_end: Color::new(40, 200, 60, 0),
+    size_start: 0.03, size_end: 0.01,
+};
+pub const SHIELD_SPARKLE: Emitter = Emitter {
+    count: 18, life: (0.6, 1.1), speed: (0.05, 0.3), up: (0.3, 0.6), spread: std::f32::consts::TAU, gravity: -0.2,
+    color_start: Color::new(120, 200, 255, 255), color_end: Color::new(40, 90, 255, 0),
+    size_start: 0.03, size_end: 0.01,
+};
+pub const CHEST_SPARKLE: Emitter = Emitter {
+    count: 24, life: (0.5, 1.0), speed: (0.2, 0.8), up: (0.6, 1.4), spread: std::f32::consts::TAU, gravity: 1.2,
+    color_start: Color::new(255, 230, 90, 255), color_end: Color::new(255, 140, 0, 0),
+    size_start: 0.03, size_end: 0.015,
+};
+pub const ROCKET_TRAIL: Emitter = Emitter {
+    count: 2, life: (0.3, 0.6), speed: (0.05, 0.3), up: (0.0, 0.15), spread: 0.8, gravity: -0.3,
+    color_start: Color::new(200, 200, 200, 180), color_end: Color::new(90, 90, 90, 0),
+    size_start: 0.03, size_end: 0.07,
+};
+pub const EXPLOSION_FIRE: Emitter = Emitter {
+    count: 40, life: (0.25, 0.55), speed: (1.0, 3.5), up: (-0.5, 1.5), spread: std::f32::consts::TAU, gravity: 1.0,
+    color_start: Color::new(255, 220, 90, 255), color_end: Color::new(220, 60, 0, 0),
+    size_start: 0.09, size_end: 0.04,
+};
+pub const EXPLOSION_SMOKE: Emitter = Emitter {
+    count: 24, life: (0.8, 1.5), speed: (0.3, 1.2), up: (0.1, 0.6), spread: std::f32::consts::TAU, gravity: -0.4,
+    color_start: Color::new(90, 85, 80, 220), color_end: Color::new(40, 40, 40, 0),
+    size_start: 0.08, size_end: 0.18,
+};
+
+pub struct Particle {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    vx: f32,
+    vy: f32,
+    vz: f32,
+    age: f32,
+    life: f32,
+    fx: &'static Emitter,
+}
+
+impl Particle {
+    fn t(&self) -> f32 { (self.age / self.life).clamp(0.0, 1.0) }
+
+    pub fn color(&self) -> Color {
+        let (a, b, t) = (self.fx.color_start, self.fx.color_end, self.t());
+        let lerp = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t) as u8;
+        Color::new(lerp(a.r, b.r), lerp(a.g, b.g), lerp(a.b, b.b), lerp(a.a, b.a))
+    }
+
+    pub fn size(&self) -> f32 {
+        self.fx.size_start + (self.fx.size_end - self.fx.size_start) * self.t()
+    }
+}
+
+/// Todas las partículas vivas del mundo (con tope `PARTICLE_MAX`).
+pub struct Particles {
+    items: VecDeque<Particle>,
+    rng: ThreadRng,
+}
+
+impl Particles {
+    pub fn new() -> Self {
+        Self { items: VecDeque::with_capacity(PARTICLE_MAX), rng: rand::thread_rng() }
+    }
+
+    /// Dispara el emisor `fx` en (x, y, z) apuntando hacia el ángulo `dir`.
+    pub fn emit(&mut self, fx: &'static Emitter, x: f32, y: f32, z: f32, dir: f32) {
+        for _ in 0..fx.count {
+            if self.items.len() >= PARTICLE_MAX { self.items.pop_front(); }
+            let a = dir + self.rng.gen_range(-0.5..=0.5) * fx.spread;
+            let speed = self.rng.gen_range(fx.speed.0..=fx.speed.1);
+            self.items.push_back(Particle {
+                x, y, z,
+                vx: a.cos() * speed,
+                vy: a.sin() * speed,
+                vz: self.rng.gen_range(fx.up.0..=fx.up.1),
+                age: 0.0,
+                life: self.rng.gen_range(fx.life.0..=fx.life.1),
+                fx,
+            });
+        }
+    }
+
+    /// Integra velocidad/gravedad; rebotan un poco en suelo/techo y se frenan en paredes.
+    pub fn update(&mut self, dt: f32, map: &Map) {
+        for p in self.items.iter_mut() {
+            p.age += dt;
+            p.vz -= p.fx.gravity * dt;
+
+            let nx = p.x + p.vx * dt;
+            let ny = p.y + p.vy * dt;
+            if map.at(nx.floor() as i32, ny.floor() as i32) > 0 {
+                p.vx = 0.0;
+                p.vy = 0.0;
+            } else {
+                p.x = nx;
+                p.y = ny;
+            }
+
+            p.z += p.vz * dt;
+            if p.z < 0.0 {
+                p.z = 0.0;
+                p.vz = -p.vz * 0.3;
+                p.vx *= 0.6;
+                p.vy *= 0.6;
+            } else if p.z > 1.0 {
+                p.z = 1.0;
+                p.vz = 0.0;
+            }
+        }
+        self.items.retain(|p| p.age < p.life);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.items.iter()
+    }
+}
@@ -1,73 +1,188 @@
 use raylib::prelude::*;
 use std::f32::consts::PI;
 use crate::map::Map;
-use crate::types::{Player, Decoration, Chest};
+use crate::types::{Player, Decoration, Chest, MoveMod};
+use crate::collision::move_circle;
+use crate::consts::PLAYER_RADIUS;
+use crate::input::{Action, Bindings};
 
 pub struct MoveCfg {
-    pub move_speed: f32,
+    pub move_speed: f32,  // velocidad caminando
+    pub crouch_mult: f32, // velocidad agachado (× move_speed)
+    pub sprint_mult: f32, // velocidad corriendo (× move_speed)
+    pub stamina_max: f32,
+    pub stamina_drain: f32,       // por segundo corriendo
+    pub stamina_regen: f32,       // por segundo descansando
+    pub stamina_regen_delay: f32, // s sin correr antes de recuperar
+    pub sprint_min: f32,          // estamina mínima para empezar a correr
     pub rot_speed: f32,
+    pub mouse_look: bool, // girar/mirar con el mouse capturado
+    pub mouse_sens: f32,  // radianes (yaw) por píxel de mouse; el pitch usa la misma escala
+    pub turn_keys: bool,  // true = las teclas de strafe giran (estilo clásico)
+    pub pitch_speed: f32, // fracción de la vista por segundo (flechas ↑/↓)
+    pub max_pitch: f32,   // límite del pitch (fracción del alto de la vista)
+    pub eye_stand: f32,   // altura de ojos de pie
+    pub eye_crouch: f32,  // altura de ojos agachado
+    pub jump_speed: f32,  // velocidad vertical inicial del salto (alturas/s)
+    pub gravity: f32,     // alturas/s²
 }
 
 pub fn handle_input(
     rl: &RaylibHandle,
+    input: &Bindings,
     player: &mut Player,
     cfg: &MoveCfg,
     dt: f32,
     map: &Map,
     decorations: &[Decoration],
-    chests: &[Chest], // <-- nuevo
+    chests: &[Chest],
 ) {
-    if rl.is_key_down(KeyboardKey::KEY_A) { player.angle -= cfg.rot_speed * dt; }
-    if rl.is_key_down(KeyboardKey::KEY_D) { player.angle += cfg.rot_speed * dt; }
+    // Mirar con el mouse (sólo si está capturado: cursor oculto mientras se juega)
+    if cfg.mouse_look && rl.is_cursor_hidden() {
+        let md = rl.get_mouse_delta();
+        player.angle += md.x * cfg.mouse_sens;
+        player.pitch -= md.y * cfg.mouse_sens;
+    }
+
+    // Strafe (por defecto A/D o stick izquierdo) o girar (opción `turn_keys`).
+    // Los ejes son analógicos con el mando: -1..1
+    let mut strafe = input.axis(rl, Action::StrafeLeft, Action::StrafeRight);
+    if cfg.turn_keys {
+        player.angle += strafe * cfg.rot_speed * dt;
+        strafe = 0.0;
+    }
+    // Girar con teclado o stick derecho (útil sin mouse)
+    player.angle += input.axis(rl, Action::TurnLeft, Action::TurnRight) * cfg.rot_speed * dt;
 
     if player.angle > PI { player.angle -= 2.0 * PI; }
     if player.angle < -PI { player.angle += 2.0 * PI; }
 
+    // Mirar arriba/abajo (y-shearing)
+    player.pitch += input.axis(rl, Action::LookDown, Action::LookUp) * cfg.pitch_speed * dt;
+    player.pitch = player.pitch.clamp(-cfg.max_pitch, cfg.max_pitch);
+
+    update_eye_height(rl, input, player, cfg, dt);
+
     let dir_x = player.angle.cos();
     let dir_y = player.angle.sin();
+    // derecha de la cámara (mismo sentido que el plano de cámara del render)
+    let right_x = -dir_y;
+    let right_y =  dir_x;
+
+    let forward = input.axis(rl, Action::MoveBack, Action::MoveForward);
+
+    // Vector de movimiento combinado, normalizado (la diagonal no va más rápido;
+    // con el stick a medias se camina más lento)
+    let mut mv_x = dir_x * forward + right_x * strafe;
+    let mut mv_y = dir_y * forward + right_y * strafe;
+    let len = (mv_x * mv_x + mv_y * mv_y).sqrt();
+
+    // Sprint (sólo hacia delante y de pie)
+    let want_sprint = input.down(rl, Action::Sprint) && forward > 0.0 && player.crouch < 0.5;
+    update_stamina(player, cfg, want_sprint, dt);
+
+    tick_move_mods(player, dt);
+    if len < 1e-6 { return; }
+
+    let speed = current_speed(player, cfg) * len.min(1.0);
+    mv_x *= speed * dt / len;
+    mv_y *= speed * dt / len;
+
+    // Círculo vs rejilla + decoraciones/cofres, con deslizamiento por paredes
+    let (nx, ny) = move_circle(map, decorations, chests, player.x, player.y, mv_x, mv_y, PLAYER_RADIUS);
+    player.x = nx;
+    player.y = ny;
+}
+
+/// Velocidad actual: caminar/agachado/correr × modificadores
+fn current_speed(player: &Player, cfg: &MoveCfg) -> f32 {
+    let stance = 1.0 + (cfg.crouch_mult - 1.0) * player.crouch;
+    let sprint = if player.sprinting { cfg.sprint_mult } else { 1.0 };
+    let mods: f32 = player.move_mods.iter().map(|m| m.mult).product();
+    cfg.move_speed * stance * sprint * mods
+}
+
+/// Gasta estamina corriendo; se recupera tras `stamina_regen_delay`.
+/// Al agotarse hay que esperar a tener `sprint_min` para volver a correr.
+fn update_stamina(player: &mut Player, cfg: &MoveCfg, want_sprint: bool, dt: f32) {
+    let can_start = player.sprinting || player.stamina >= cfg.sprint_min;
+    player.sprinting = want_sprint && can_start && player.stamina > 0.0;
 
-    let mut next_px = player.x;
-    let mut next_py = player.y;
-
-    let radius = 0.2_f32;
-
-    // helper de colisión con decoraciones bloqueantes
-    let collides_deco = |x: f32, y: f32| -> bool {
-        decorations.iter().any(|d| {
-            if !d.is_blocking() { return false; }
-            let dx = d.x - x;
-            let dy = d.y - y;
-            (dx*dx + dy*dy).sqrt() < (radius + d.radius)
-        })
-    };
-
-    let collides_chest = |x: f32, y: f32| -> bool {
-    chests.iter().any(|c| {
-        if !c.is_blocking() { return false; }
-        let dx = c.x - x;
-        let dy = c.y - y;
-        (dx*dx + dy*dy) < ( (radius + c.radius)*(radius + c.radius) )
-    })
-};
-
-    // eje X
-    if map.at(next_px.floor() as i32, player.y.floor() as i32) == 0 && !collides_deco(next_px, player.y) {
-        player.x = next_px;
+    if player.sprinting {
+        player.stamina = (player.stamina - cfg.stamina_drain * dt).max(0.0);
+        player.stamina_cd = cfg.stamina_regen_delay;
+    } else if player.stamina_cd > 0.0 {
+        player.stamina_cd -= dt;
+    } else {
+        player.stamina = (player.stamina + cfg.stamina_regen * dt).min(cfg.stamina_max);
     }
-    // eje Y
-    if map.at(player.x.floor() as i32, next_py.floor() as i32) == 0 && !collides_deco(player.x, next_py) {
-        player.y = next_py;
+}
+
+impl Player {
+    /// Pone (o reemplaza) el modificador de velocidad `id`.
+    pub fn set_move_mod(&mut self, id: &'static str, mult: f32, time: Option<f32>) {
+        self.clear_move_mod(id);
+        self.move_mods.push(MoveMod { id, mult, time_left: time });
+    }
+
+    pub fn clear_move_mod(&mut self, id: &'static str) {
+        self.move_mods.retain(|m| m.id != id);
+    }
+
+    /// Pasa al siguiente (`dir` = 1) o anterior (`dir` = -1) slot ocupado.
+    /// Desde la mano vacía empieza por el primero/último.
+    pub fn cycle_slot(&mut self, dir: i32) {
+        let n = self.slots.len() as i32;
+        let mut i = match self.selected {
+            Some(si) => si as i32,
+            None if dir > 0 => -1,
+            None => n,
+        };
+        for _ in 0..n {
+            i = (i + dir).rem_euclid(n);
+            if self.slots[i as usize].is_some() {
+                self.selected = Some(i as usize);
+                return;
+            }
+        }
+    }
+}
+
+/// Descuenta la duración de los modificadores temporales y quita los vencidos
+fn tick_move_mods(player: &mut Player, dt: f32) {
+    for m in player.move_mods.iter_mut() {
+        if let Some(t) = &mut m.time_left { *t -= dt; }
     }
+    player.move_mods.retain(|m| m.time_left.map_or(true, |t| t > 0.0));
+}
+
+/// Agacharse (mantener) y saltar. Deja el resultado en `player.eye_z`.
+fn update_eye_height(rl: &RaylibHandle, input: &Bindings, player: &mut Player, cfg: &MoveCfg, dt: f32) {
+    let on_ground = player.jump_z <= 0.0;
 
-    if rl.is_key_down(KeyboardKey::KEY_W) {
-        next_px += dir_x * cfg.move_speed * dt;
-        next_py += dir_y * cfg.move_speed * dt;
+    let want_crouch = input.down(rl, Action::Crouch);
+    let target = if want_crouch { 1.0 } else { 0.0 };
+    player.crouch += (target - player.crouch) * (dt * 12.0).min(1.0);
+
+    if on_ground && !want_crouch && input.pressed(rl, Action::Jump) {
+        player.vz = cfg.jump_speed;
     }
-    if rl.is_key_down(KeyboardKey::KEY_S) {
-        next_px -= dir_x * cfg.move_speed * dt;
-        next_py -= dir_y * cfg.move_speed * dt;
+    player.vz -= cfg.gravity * dt;
+    player.jump_z += player.vz * dt;
+    if player.jump_z <= 0.0 {
+        player.jump_z = 0.0;
+        player.vz = 0.0;
     }
 
-    if map.at(next_px.floor() as i32, player.y.floor() as i32) == 0 { player.x = next_px; }
-    if map.at(player.x.floor() as i32, next_py.floor() as i32) == 0 { player.y = next_py; }
+    let base = cfg.eye_stand + (cfg.eye_crouch - cfg.eye_stand) * player.crouch;
+    player.eye_z = (base + player.jump_z).min(0.95);
+}
+
+/// Balanceo del arma: `moved` = distancia recorrida este frame.
+/// La intensidad sigue suavemente a la velocidad relativa a `cfg.move_speed`.
+pub fn update_weapon_bob(player: &mut Player, moved: f32, dt: f32, cfg: &MoveCfg) {
+    if dt <= 0.0 { return; }
+    let target = (moved / dt / cfg.move_speed).clamp(0.0, 1.5);
+    player.bob_amp += (target - player.bob_amp) * (dt * 10.0).min(1.0);
+    player.bob_t = (player.bob_t + dt * 9.0 * player.bob_amp) % (2.0 * PI);
 }
@@ -0,0 +1,292 @@
+use crate::map::Map;
+use crate::types::{Player, Enemy, EnemyState, Decoration, Chest, WallFace, WallHit};
+use crate::ai::{has_los, apply_damage};
+use crate::audio::Audio;
+use crate::decals::{Decals, DecalKind};
+use crate::particles::{self, Particles};
+use crate::screen_fx::ScreenFx;
+use crate::consts::{
+    ENEMY_RADIUS, ROCKET_SPEED, ROCKET_RADIUS, ROCKET_LIFE,
+    SPLASH_RADIUS, SPLASH_SELF_MULT, SPLASH_KNOCKBACK,
+    GRENADE_FUSE, GRENADE_RADIUS, GRENADE_GRAVITY, GRENADE_BOUNCE, GRENADE_FRICTION,
+};
+
+/// Cohete en vuelo: recto y a altura constante (z en alturas de pared)
+pub struct Rocket {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub dir: f32,
+    damage: i32, // daño en el centro de la explosión
+    age: f32,
+}
+
+/// Granada lanzada: vuela con gravedad, rebota en paredes y suelo y revienta
+/// al acabarse la mecha (z en alturas de pared)
+pub struct Grenade {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    vx: f32,
+    vy: f32,
+    vz: f32,
+    fuse: f32,
+    damage: i32,
+    landed: bool, // ya tocó el suelo: a partir de ahí los enemigos huyen de ella
+}
+
+/// Dónde reventó un cohete o una granada. `wall` es la cara que tocó (para la quemadura).
+pub struct Explosion {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub damage: i32,
+    pub wall: Option<WallHit>,
+}
+
+/// Proyectiles vivos en el mundo
+pub struct Projectiles {
+    items: Vec<Rocket>,
+    grenades: Vec<Grenade>,
+}
+
+impl Projectiles {
+    pub fn new() -> Self {
+        Self { items: Vec::new(), grenades: Vec::new() }
+    }
+
+    /// Lanza un cohete desde (x, y, z) hacia el ángulo `dir`
+    pub fn fire(&mut self, x: f32, y: f32, z: f32, dir: f32, damage: i32) {
+        self.items.push(Rocket { x, y, z, dir, damage, age: 0.0 });
+    }
+
+    /// Lanza una granada desde (x, y, z) hacia `dir` con velocidad horizontal `speed`
+    /// y vertical `vz`; la mecha empieza a contar ya
+    pub fn throw(&mut self, x: f32, y: f32, z: f32, dir: f32, speed: f32, vz: f32, damage: i32) {
+        self.grenades.push(Grenade {
+            x, y, z,
+            vx: dir.cos() * speed,
+            vy: dir.sin() * speed,
+            vz,
+            fuse: GRENADE_FUSE,
+            damage,
+            landed: false,
+        });
+    }
+
+    /// Avanza los cohetes en sub-pasos (no atraviesan nada con un dt grande) y
+    /// devuelve los que chocaron contra pared, decoración, cofre o enemigo vivo,
+    /// más las granadas a las que se les acabó la mecha.
+    pub fn update(&mut self, dt: f32, map: &Map, decorations: &[Decoration], chests: &[Chest], enemies: &[Enemy]) -> Vec<Explosion> {
+        let mut out = Vec::new();
+
+        self.items.retain_mut(|r| {
+            r.age += dt;
+            let (dx, dy) = (r.dir.cos(), r.dir.sin());
+            let dist = ROCKET_SPEED * dt;
+            let steps = ((dist / ROCKET_RADIUS).ceil() as usize).max(1);
+            let step = dist / steps as f32;
+
+            for _ in 0..steps {
+                let (nx, ny) = (r.x + dx * step, r.y + dy * step);
+
+                // Pared: explota en el último punto libre y anota la cara que tocó
+                if map.at(nx.floor() as i32, ny.floor() as i32) > 0 {
+                    let wall = wall_hit(map, (r.x, r.y), (nx, ny), r.z);
+                    out.push(Explosion { x: r.x, y: r.y, z: r.z, damage: r.damage, wall });
+                    return false;
+                }
+
+                let touches = |ox: f32, oy: f32, orad: f32| {
+                    (nx - ox).powi(2) + (ny - oy).powi(2) <= (ROCKET_RADIUS + orad).powi(2)
+                };
+                let hit = enemies.iter().any(|e| e.is_alive() && touches(e.x, e.y, ENEMY_RADIUS))
+                    || decorations.iter().any(|d| d.is_blocking() && touches(d.x, d.y, d.radius))
+                    || chests.iter().any(|c| c.is_blocking() && touches(c.x, c.y, c.radius));
+
+                r.x = nx;
+                r.y = ny;
+                if hit {
+                    out.push(Explosion { x: r.x, y: r.y, z: r.z, damage: r.damage, wall: None });
+                    return false;
+                }
+            }
+
+            // Sin chocar con nada: revienta en el aire al agotar su vida
+            if r.age >= ROCKET_LIFE {
+                out.push(Explosion { x: r.x, y: r.y, z: r.z, damage: r.damage, wall: None });
+                return false;
+            }
+            true
+        });
+
+        self.grenades.retain_mut(|g| {
+            g.fuse -= dt;
+            let speed = g.vx.hypot(g.vy).max(g.vz.abs());
+            let steps = ((speed * dt / GRENADE_RADIUS).ceil() as usize).clamp(1, 64);
+            let h = dt / steps as f32;
+            for _ in 0..steps {
+                step_grenade(g, h, map);
+            }
+
+            if g.fuse <= 0.0 {
+                out.push(Explosion { x: g.x, y: g.y, z: g.z, damage: g.damage, wall: None });
+                return false;
+            }
+            true
+        });
+
+        out
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.grenades.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Rocket> {
+        self.items.iter()
+    }
+
+    pub fn grenades(&self) -> impl Iterator<Item = &Grenade> {
+        self.grenades.iter()
+    }
+
+    /// Posiciones de las granadas que ya están en el suelo (de las que huye la IA)
+    pub fn landed_grenades(&self) -> Vec<(f32, f32)> {
+        self.grenades.iter().filter(|g| g.landed).map(|g| (g.x, g.y)).collect()
+    }
+}
+
+/// Un sub-paso de la física de la granada: gravedad, rebote por eje contra las
+/// celdas sólidas del mapa, rebote en suelo y techo y rozamiento al rodar.
+fn step_grenade(g: &mut Grenade, h: f32, map: &Map) {
+    let solid = |x: f32, y: f32| map.at(x.floor() as i32, y.floor() as i32) > 0;
+
+    // X e Y por separado: el eje que choca invierte su velocidad y el otro sigue
+    let nx = g.x + g.vx * h;
+    if solid(nx + GRENADE_RADIUS * g.vx.signum(), g.y) {
+        g.vx = -g.vx * GRENADE_BOUNCE;
+    } else {
+        g.x = nx;
+    }
+    let ny = g.y + g.vy * h;
+    if solid(g.x, ny + GRENADE_RADIUS * g.vy.signum()) {
+        g.vy = -g.vy * GRENADE_BOUNCE;
+    } else {
+        g.y = ny;
+    }
+
+    g.vz -= GRENADE_GRAVITY * h;
+    g.z += g.vz * h;
+    if g.z <= GRENADE_RADIUS {
+        g.z = GRENADE_RADIUS;
+        g.landed = true;
+        // rebota si cae con fuerza; si no, se queda rodando
+        g.vz = if g.vz < -1.0 { -g.vz * GRENADE_BOUNCE } else { 0.0 };
+    } else if g.z >= 1.0 - GRENADE_RADIUS && g.vz > 0.0 {
+        // techo (también bajo cielo abierto: por encima de las paredes no hay nada)
+        g.z = 1.0 - GRENADE_RADIUS;
+        g.vz = -g.vz * GRENADE_BOUNCE;
+    }
+
+    if g.z <= GRENADE_RADIUS + 1e-3 {
+        let f = (1.0 - GRENADE_FRICTION * h).max(0.0);
+        g.vx *= f;
+        g.vy *= f;
+    }
+}
+
+/// Cara de la pared entre `from` (libre) y `to` (dentro de la pared), a altura `z`.
+/// Si cruza en diagonal por una esquina, gana la cara X si esa celda es sólida.
+fn wall_hit(map: &Map, from: (f32, f32), to: (f32, f32), z: f32) -> Option<WallHit> {
+    let (cx0, cy0) = (from.0.floor() as i32, from.1.floor() as i32);
+    let (cx1, cy1) = (to.0.floor() as i32, to.1.floor() as i32);
+    let x_face = cx1 != cx0 && (cy1 == cy0 || map.at(cx1, cy0) > 0);
+
+    let (face, u) = if x_face {
+        let bx = cx0.max(cx1) as f32;
+        let t = (bx - from.0) / (to.0 - from.0);
+        let y = from.1 + (to.1 - from.1) * t;
+        (WallFace { cell_x: cx1, cell_y: y.floor() as i32, normal: (cx0 - cx1, 0) }, y - y.floor())
+    } else if cy1 != cy0 {
+        let by = cy0.max(cy1) as f32;
+        let t = (by - from.1) / (to.1 - from.1);
+        let x = from.0 + (to.0 - from.0) * t;
+        (WallFace { cell_x: x.floor() as i32, cell_y: cy1, normal: (0, cy0 - cy1) }, x - x.floor())
+    } else {
+        return None;
+    };
+    Some(WallHit { face, u, v: 1.0 - z })
+}
+
+/// Daño en área: cae linealmente hasta SPLASH_RADIUS y las paredes opacas lo tapan
+/// (`has_los` desde el centro). Hiere a los enemigos y al propio jugador (escudo y
+/// luego vida, a SPLASH_SELF_MULT) y los empuja hacia fuera. Devuelve los kills.
+pub fn explode(
+    ex: &Explosion,
+    map: &Map,
+    player: &mut Player,
+    enemies: &mut [Enemy],
+    decals: &mut Decals,
+    particles: &mut Particles,
+    audio: &Audio,
+    fx: &mut ScreenFx,
+) -> u32 {
+    audio.play_explosion();
+    particles.emit(&particles::EXPLOSION_FIRE, ex.x, ex.y, ex.z, 0.0);
+    particles.emit(&particles::EXPLOSION_SMOKE, ex.x, ex.y, ex.z, 0.0);
+    if let Some(hit) = ex.wall {
+        decals.add(hit, DecalKind::Scorch);
+    }
+
+    // (falloff 0..1, dirección hacia fuera) si el punto queda dentro del radio y a la vista
+    let reach = |x: f32, y: f32| -> Option<(f32, f32, f32)> {
+        let (dx, dy) = (x - ex.x, y - ex.y);
+        let d = dx.hypot(dy);
+        if d >= SPLASH_RADIUS || !has_los(map, ex.x, ex.y, x, y) { return None; }
+        let (nx, ny) = if d > 1e-3 { (dx / d, dy / d) } else { (0.0, 0.0) };
+        Some((1.0 - d / SPLASH_RADIUS, nx, ny))
+    };
+
+    let mut kills = 0;
+    let mut hit_any = false;
+    for e in enemies.iter_mut() {
+        let Some((f, nx, ny)) = reach(e.x, e.y) else { continue };
+        e.knock.0 += nx * SPLASH_KNOCKBACK * f;
+        e.knock.1 += ny * SPLASH_KNOCKBACK * f;
+        if !e.is_alive() { continue; }
+
+        hit_any = true;
+        e.hp -= (ex.damage as f32 * f).round() as i32;
+        let away = ny.atan2(nx);
+        if e.hp <= 0 {
+            e.state = EnemyState::Dead;
+            kills += 1; // ✅ contamos el kill sólo al pasar a muerto
+            audio.play_enemy_death();
+            particles.emit(&particles::GIBS, e.x, e.y, 0.45, away);
+        } else {
+            e.flash_timer = 0.1;
+            audio.play_enemy_hurt();
+            particles.emit(&particles::BLOOD, e.x, e.y, 0.55, away);
+        }
+    }
+    if hit_any {
+        fx.on_hit(false);
+    }
+
+    // El propio jugador: daño reducido, pero el empuje entero
+    if let Some((f, nx, ny)) = reach(player.x, player.y) {
+        player.knock.0 += nx * SPLASH_KNOCKBACK * f;
+        player.knock.1 += ny * SPLASH_KNOCKBACK * f;
+        let dmg = (ex.damage as f32 * f * SPLASH_SELF_MULT).round() as i32;
+        let (took, died_now) = apply_damage(player, dmg, fx, (ex.x, ex.y));
+        if died_now {
+            audio.play_player_death();
+        } else if took {
+            audio.play_player_hurt();
+        }
+    }
+
+    kills
+}
@@ -1,11 +1,14 @@
 use raylib::prelude::*;
-use crate::consts::{SCREEN_W, SCREEN_H};
 use crate::map::Map;
 use crate::types::{
-    Enemy, EnemyState, DrawnSprite, Pickup, PickupKind,
-    Decoration, DecoKind, Chest,
-    Rarity, WeaponType,
+    Enemy, EnemyState, DrawnSprite, Pickup, PickupKind, Item, MeleeType, ConsumableType,
+    Decoration, DecoKind, Chest, Checkpoint,
+    Rarity, Viewport, WallFace, WallHit,
 };
+use crate::decals::Decals;
+use crate::particles::Particles;
+use crate::projectiles::Projectiles;
+use crate::consts::{GRENADE_RADIUS, GRENADE_COLOR, VIEW_INTERNAL_H, VIEW_SMOOTH};
 
 pub struct RenderParams {
     pub fov: f32,
@@ -13,11 +16,363 @@ pub struct RenderParams {
     pub player_x: f32,
     pub player_y: f32,
     pub player_angle: f32,
+    // tamaño (en píxeles) de la superficie donde se dibuja la vista 3D
+    pub view_w: i32,
+    pub view_h: i32,
+    // mirar arriba/abajo (y-shearing): desplazamiento del horizonte en fracción de view_h
+    pub pitch: f32,
+    // altura de los ojos (0 = suelo, 0.5 = de pie, 1 = techo)
+    pub eye_z: f32,
+}
+
+impl RenderParams {
+    /// Fila de pantalla del horizonte (altura de los ojos)
+    pub fn horizon(&self) -> f32 {
+        self.view_h as f32 * (0.5 + self.pitch)
+    }
+}
+
+/// Resolución interna de la vista 3D. Se dibuja en un render target de este
+/// tamaño y luego se escala a la ventana (el HUD sigue a resolución nativa).
+/// Sólo se fija el alto: el ancho sale del aspecto de la ventana, así una
+/// ventana ultrawide no deforma la imagen.
+pub struct ViewCfg {
+    pub internal_h: i32,
+    pub smooth: bool, // true = bilinear, false = nearest (pixelado retro)
+}
+
+impl ViewCfg {
+    pub fn defaults() -> Self {
+        Self { internal_h: VIEW_INTERNAL_H, smooth: VIEW_SMOOTH }
+    }
+
+    /// Lee `internal_h = 200` y `filter = nearest|bilinear` (una por línea, `#` comenta).
+    /// Lo que no aparezca se queda con el valor de fábrica.
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, String> {
+        let txt = std::fs::read_to_string(&path).map_err(|e| format!("No se pudo leer la config de vídeo: {e}"))?;
+        let mut out = Self::defaults();
+
+        for (n, line) in txt.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() { continue; }
+
+            let (k, v) = line.split_once('=')
+                .ok_or_else(|| format!("Vídeo, línea {}: falta '='", n + 1))?;
+            let (k, v) = (k.trim(), v.trim());
+            let size = |v: &str| v.parse().ok().filter(|x: &i32| *x > 0)
+                .ok_or_else(|| format!("Vídeo, línea {}: '{k}' debe ser un entero > 0", n + 1));
+            match k {
+                "internal_h" => out.internal_h = size(v)?,
+                "filter" => {
+                    out.smooth = match v.to_ascii_lowercase().as_str() {
+                        "nearest" => false,
+                        "bilinear" => true,
+                        _ => return Err(format!("Vídeo, línea {}: filtro desconocido '{v}' (nearest/bilinear)", n + 1)),
+                    };
+                }
+                other => return Err(format!("Vídeo, línea {}: clave desconocida '{other}'", n + 1)),
+            }
+        }
+        Ok(out)
+    }
+
+    /// (ancho, alto) internos para una ventana de tamaño `vp`.
+    pub fn internal_size(&self, vp: Viewport) -> (i32, i32) {
+        let h = self.internal_h.clamp(1, vp.h);
+        let w = ((h as f32) * vp.w as f32 / vp.h as f32).round() as i32;
+        (w.max(1), h)
+    }
+
+    /// Crea el render target de la vista con el filtro elegido.
+    pub fn load_target(&self, rl: &mut RaylibHandle, thread: &RaylibThread, vp: Viewport) -> RenderTexture2D {
+        let (w, h) = self.internal_size(vp);
+        let rt = rl
+            .load_render_texture(thread, w as u32, h as u32)
+            .expect("No se pudo crear el render target de la vista 3D");
+        let filter = if self.smooth {
+            TextureFilter::TEXTURE_FILTER_BILINEAR
+        } else {
+            TextureFilter::TEXTURE_FILTER_POINT
+        };
+        rt.set_texture_filter(thread, filter);
+        rt
+    }
+}
+
+/// FOV “Hor+”: `base_fov` es el FOV horizontal en 16:9. Se conserva el FOV
+/// vertical y el horizontal crece con el aspecto (en ultrawide se ve más a
+/// los lados sin estirar las paredes). Devuelve (fov, proj_dist).
+pub fn camera_fov(base_fov: f32, view_w: i32, view_h: i32) -> (f32, f32) {
+    let base_aspect = 16.0 / 9.0;
+    let half_v = ((base_fov / 2.0).tan() / base_aspect).atan();
+    let aspect = view_w as f32 / view_h.max(1) as f32;
+    let fov = 2.0 * (half_v.tan() * aspect).atan();
+    let proj_dist = (view_w as f32 / 2.0) / (fov / 2.0).tan();
+    (fov, proj_dist)
+}
+
+/// Escala el render target de la vista 3D a toda la ventana.
+/// La textura de un render target está invertida en Y, por eso el alto negativo.
+pub fn blit_view(d: &mut RaylibDrawHandle, view: &RenderTexture2D, vp: Viewport) {
+    let src = Rectangle { x: 0.0, y: 0.0, width: view.width() as f32, height: -(view.height() as f32) };
+    let dest = Rectangle { x: 0.0, y: 0.0, width: vp.w as f32, height: vp.h as f32 };
+    d.draw_texture_pro(view, src, dest, Vector2::new(0.0, 0.0), 0.0, Color::WHITE);
+}
+
+/// Columna de pared ya proyectada (origen en textura + destino en pantalla).
+#[derive(Clone, Copy)]
+struct WallSlice<'a> {
+    tex: &'a Texture2D,
+    src: Rectangle,
+    dest: Rectangle,
+    tint: Color,
+    depth: f32, // distancia (la misma métrica que el zbuffer)
+    face: WallFace,
+    u: f32,        // punto de impacto a lo largo de la cara (0..1, sin espejar)
+    line_top: f32, // fila (sin recortar) del borde superior de la pared
+    line_h: f32,   // alto proyectado de la pared
+}
+
+impl WallSlice<'_> {
+    fn draw(&self, d: &mut impl RaylibDraw) {
+        d.draw_texture_pro(self.tex, self.src, self.dest, Vector2::new(0.0, 0.0), 0.0, self.tint);
+    }
+
+    /// Datos de la pared para ubicar impactos en esta columna
+    fn column(&self) -> WallColumn {
+        WallColumn { face: self.face, u: self.u, line_top: self.line_top, line_h: self.line_h }
+    }
+}
+
+/// Pared opaca de una columna ya dibujada (sin textura): basta para ubicar impactos
+#[derive(Clone, Copy)]
+pub struct WallColumn {
+    face: WallFace,
+    u: f32,
+    line_top: f32,
+    line_h: f32,
+}
+
+impl WallColumn {
+    /// Impacto en la fila de pantalla `row` de esta columna
+    fn hit_at(&self, row: f32) -> WallHit {
+        WallHit { face: self.face, u: self.u, v: (row - self.line_top) / self.line_h }
+    }
+}
+
+/// Marcas de impacto de la cara de `slice` que cruzan esta columna.
+/// Cada marca es un disco en espacio de textura: se pinta el tramo vertical
+/// que corta la columna (borde suave + centro oscuro).
+fn draw_decals_column(d: &mut impl RaylibDraw, decals: &Decals, slice: &WallSlice, view_h: i32) {
+    let x = slice.dest.x as i32;
+    for (dc, alpha) in decals.on_face(slice.face) {
+        let r = dc.kind.radius();
+        let du = slice.u - dc.u;
+        if du.abs() >= r { continue; }
+        let half = (r * r - du * du).sqrt();
+        let (edge, core) = dc.kind.colors();
+
+        for (h, col) in [(half, edge), (half * 0.5, core)] {
+            let y0 = (slice.line_top + (dc.v - h) * slice.line_h).max(0.0) as i32;
+            let y1 = (slice.line_top + (dc.v + h) * slice.line_h).min(view_h as f32) as i32;
+            if y1 <= y0 { continue; }
+            let a = (col.a as f32 * alpha) as u8;
+            d.draw_line(x, y0, x, y1, Color::new(col.r, col.g, col.b, a));
+        }
+    }
+}
+
+/// Proyecta la cara de la celda `cell = (map_x, map_y, side)` que golpea el
+/// rayo de la columna `x`. None si queda fuera de la pantalla.
+fn wall_slice<'t>(
+    params: &RenderParams,
+    map: &Map,
+    wall_textures: &'t [Texture2D],
+    cell: (i32, i32, i32),
+    step: (i32, i32),
+    ray_dir: (f32, f32),
+    x: i32,
+    horizon: f32,
+) -> Option<WallSlice<'t>> {
+    let (map_x, map_y, side) = cell;
+    let (step_x, step_y) = step;
+    let (ray_dir_x, ray_dir_y) = ray_dir;
+
+    // distancia perpendicular (evita fisheye)
+    let perp_dist = if side == 0 {
+        ((map_x as f32 - params.player_x) + (1 - step_x) as f32 / 2.0) / ray_dir_x
+    } else {
+        ((map_y as f32 - params.player_y) + (1 - step_y) as f32 / 2.0) / ray_dir_y
+    }.abs();
+
+    // Altura proyectada (float)
+    let line_h_f = params.proj_dist / perp_dist;
+
+    // Rango "real" sin recortar (la pared va de z=0 a z=1; los ojos están en eye_z)
+    let raw_start = horizon - (1.0 - params.eye_z) * line_h_f;
+    let raw_end   = horizon + params.eye_z * line_h_f;
+
+    // Rango visible
+    let vis_start = raw_start.max(0.0) as i32;
+    let vis_end   = raw_end.min((params.view_h - 1) as f32) as i32;
+    if vis_end < vis_start { return None; }
+
+    // id de pared y textura
+    let tile_id = map.at(map_x, map_y) as usize; // 1..N
+    let tex = if tile_id == 0 {
+        &wall_textures[0]
+    } else {
+        wall_textures.get(tile_id - 1).unwrap_or(&wall_textures[0])
+    };
+
+    // Coordenada “x” en la textura (punto de impacto)
+    let mut wall_x = if side == 0 {
+        params.player_y + perp_dist * ray_dir_y
+    } else {
+        params.player_x + perp_dist * ray_dir_x
+    };
+    wall_x -= wall_x.floor();
+
+    let face = WallFace {
+        cell_x: map_x,
+        cell_y: map_y,
+        normal: if side == 0 { (-step_x, 0) } else { (0, -step_y) },
+    };
+
+    let tex_w = tex.width;
+    let tex_h = tex.height;
+    let mut tex_x = (wall_x * tex_w as f32) as i32;
+
+    // Corregir espejo según cara
+    if side == 0 && ray_dir_x > 0.0 { tex_x = tex_w - tex_x - 1; }
+    if side == 1 && ray_dir_y < 0.0 { tex_x = tex_w - tex_x - 1; }
+
+    // Mapeo vertical correcto al clipear
+    let tex_step = tex_h as f32 / line_h_f;
+    let mut tex_y_start = 0.0_f32;
+    if raw_start < 0.0 {
+        tex_y_start = -raw_start * tex_step;
+    }
+    let visible_px = (vis_end - vis_start + 1) as f32;
+    let src_h = visible_px * tex_step;
+
+    // Sombrear caras Y para profundidad
+    let tint = if side == 1 { Color::GRAY } else { Color::WHITE };
+
+    Some(WallSlice {
+        tex,
+        src:  Rectangle { x: tex_x as f32, y: tex_y_start, width: 1.0, height: src_h },
+        dest: Rectangle { x: x as f32,    y: vis_start as f32, width: 1.0, height: visible_px },
+        tint,
+        depth: perp_dist,
+        face,
+        u: wall_x,
+        line_top: raw_start,
+        line_h: line_h_f,
+    })
+}
+
+// Color del techo en celdas techadas (capa @ceiling del mapa)
+const CEILING_COLOR: Color = Color::new(45, 45, 50, 255);
+
+/// Dibuja las filas `[0, ceil_end)` de una columna: para cada fila se busca
+/// la celda del techo que se ve (techo a 1 - eye_z sobre los ojos) y se agrupan
+/// tramos consecutivos. Los tramos abiertos al cielo muestran el panorama
+/// cilíndrico: su x sale del ángulo del rayo y su base se apoya en el horizonte.
+fn draw_ceiling_column(
+    d: &mut impl RaylibDraw,
+    map: &Map,
+    params: &RenderParams,
+    sky_texture: Option<&Texture2D>,
+    x: i32,
+    ray_angle: f32,
+    ceil_end: i32,
+    horizon: f32,
+) {
+    let ceil_end = ceil_end.min(horizon.floor() as i32);
+    if ceil_end <= 0 { return; }
+
+    let ray_dir_x = ray_angle.cos();
+    let ray_dir_y = ray_angle.sin();
+
+    // ¿la fila `y` ve cielo? (el techo está a 1 - eye_z sobre los ojos)
+    let open_at = |y: i32| -> bool {
+        let dist = (1.0 - params.eye_z) * params.proj_dist / (horizon - y as f32 - 0.5).max(0.5);
+        let wx = params.player_x + ray_dir_x * dist;
+        let wy = params.player_y + ray_dir_y * dist;
+        map.open_to_sky(wx.floor() as i32, wy.floor() as i32)
+    };
+
+    let mut y0 = 0;
+    while y0 < ceil_end {
+        let open = open_at(y0);
+        let mut y1 = y0 + 1;
+        while y1 < ceil_end && open_at(y1) == open { y1 += 1; }
+
+        if open {
+            draw_sky_run(d, params, sky_texture, x, ray_angle, y0, y1, horizon);
+        } else {
+            d.draw_line(x, y0, x, y1, CEILING_COLOR);
+        }
+        y0 = y1;
+    }
+}
+
+/// Tramo de cielo `[y0, y1)` en la columna `x`. El panorama cubre 360° a lo
+/// ancho; su escala vertical es la misma que la horizontal (px por radián),
+/// y por encima del borde superior se estira la primera fila de la textura.
+fn draw_sky_run(
+    d: &mut impl RaylibDraw,
+    params: &RenderParams,
+    sky_texture: Option<&Texture2D>,
+    x: i32,
+    ray_angle: f32,
+    y0: i32,
+    y1: i32,
+    horizon: f32,
+) {
+    let Some(tex) = sky_texture else {
+        d.draw_line(x, y0, x, y1, Color::DARKBLUE);
+        return;
+    };
+
+    let tau = std::f32::consts::TAU;
+    let tex_w = tex.width as f32;
+    let tex_h = tex.height as f32;
+    let tex_x = (ray_angle.rem_euclid(tau) / tau * tex_w).floor().min(tex_w - 1.0);
+
+    // píxeles de pantalla por texel
+    let px_per_rad = params.view_w as f32 / params.fov;
+    let scale = px_per_rad * tau / tex_w;
+    let sky_top = horizon - tex_h * scale;
+
+    let (y0, y1) = (y0 as f32, y1 as f32);
+    if y0 < sky_top {
+        let end = y1.min(sky_top);
+        let src  = Rectangle { x: tex_x, y: 0.0, width: 1.0, height: 1.0 };
+        let dest = Rectangle { x: x as f32, y: y0, width: 1.0, height: end - y0 };
+        d.draw_texture_pro(tex, src, dest, Vector2::new(0.0, 0.0), 0.0, Color::WHITE);
+    }
+    let start = y0.max(sky_top);
+    if y1 > start {
+        let src  = Rectangle { x: tex_x, y: (start - sky_top) / scale, width: 1.0, height: (y1 - start) / scale };
+        let dest = Rectangle { x: x as f32, y: start, width: 1.0, height: y1 - start };
+        d.draw_texture_pro(tex, src, dest, Vector2::new(0.0, 0.0), 0.0, Color::WHITE);
+    }
 }
 
 pub struct RenderOutput {
     pub zbuffer: Vec<f32>,
     pub drawn: Vec<DrawnSprite>, // rangos visibles de ENEMIGOS para hitscan
+    pub walls: Vec<Option<WallColumn>>, // pared opaca de cada columna (para marcas de impacto)
+}
+
+impl RenderOutput {
+    /// Punto de la pared opaca en el píxel (x, row) de la vista, si la columna tiene pared
+    pub fn wall_hit(&self, x: i32, row: f32) -> Option<WallHit> {
+        let col = self.walls.get(usize::try_from(x).ok()?)?;
+        col.map(|c| c.hit_at(row))
+    }
 }
 
 /// Texturas de pickups (vida/escudo/armas/munición genérica)
@@ -27,11 +382,10 @@ pub struct PickupTextures<'a> {
     pub shield_small: Option<&'a Texture2D>,
     pub shield_big:   Option<&'a Texture2D>,
     // Armas (íconos)
-    pub weapon_pistol:  Option<&'a Texture2D>,
-    pub weapon_smg:     Option<&'a Texture2D>,
-    pub weapon_rifle:   Option<&'a Texture2D>,
-    pub weapon_shotgun: Option<&'a Texture2D>,
-    pub weapon_rocket:  Option<&'a Texture2D>,
+    pub weapons: Vec<&'a Texture2D>, // una por arma del catálogo (índice = `WeaponType`)
+    pub knife: Option<&'a Texture2D>,
+    pub bat:   Option<&'a Texture2D>,
+    pub grenade: Option<&'a Texture2D>, // pickup y también la granada en vuelo
     // Munición (una textura para todos)
     pub ammo_generic:   Option<&'a Texture2D>,
 }
@@ -83,7 +437,7 @@ fn rarity_glow(r: Rarity) -> Color {
 }
 
 pub fn draw_frame<'a>(
-    d: &mut RaylibDrawHandle,
+    d: &mut impl RaylibDraw,
     _thread: &RaylibThread,
     enemies: &[Enemy],
     params: &RenderParams,
@@ -91,25 +445,40 @@ pub fn draw_frame<'a>(
     pickups: &[Pickup],
     decorations: &[Decoration],
     chests: &[Chest],
+    checkpoints: &[Checkpoint],          // mástiles de checkpoint (verde = activo)
     wall_textures: &[Texture2D],         // id de pared 1 → index 0
     pickup_textures: &PickupTextures<'a>,// texturas de pickups
     chest_textures: &ChestTextures<'a>,  // texturas de cofres
     enemy_textures: &EnemyTextures<'a>,  // texturas + animación de enemigos
+    sky_texture: Option<&Texture2D>,     // panorama del cielo (None = color plano)
+    decals: &Decals,                     // marcas de impacto en paredes
+    particles: &Particles,               // partículas (billboards con zbuffer)
+    projectiles: &Projectiles,           // cohetes en vuelo
 ) -> RenderOutput {
-    let mut zbuffer: Vec<f32> = vec![f32::INFINITY; SCREEN_W as usize];
+    let view_w = params.view_w;
+    let view_h = params.view_h;
+
+    let mut zbuffer: Vec<f32> = vec![f32::INFINITY; view_w as usize];
     let mut drawn: Vec<DrawnSprite> = Vec::new();
+    let mut walls: Vec<Option<WallColumn>> = vec![None; view_w as usize];
 
     let dir_x = params.player_angle.cos();
     let dir_y = params.player_angle.sin();
 
-    // Fondo (cielo/suelo)
-    d.draw_rectangle(0, 0, SCREEN_W, SCREEN_H / 2, Color::DARKBLUE);
-    d.draw_rectangle(0, SCREEN_H / 2, SCREEN_W, SCREEN_H / 2, Color::DARKBROWN);
+    // Línea del horizonte (fila de pantalla a la altura de los ojos; se mueve con el pitch)
+    let horizon = params.horizon();
+
+    // Fondo (suelo). El techo/cielo se dibuja por columna más abajo.
+    let floor_top = (horizon as i32).clamp(0, view_h);
+    d.draw_rectangle(0, floor_top, view_w, view_h - floor_top, Color::DARKBROWN);
+
+    // Columnas de paredes see-through (rejillas/vallas/ventanas) pendientes de pintar
+    let mut masked: Vec<WallSlice> = Vec::new();
 
     // ----------------- PAREDES: raycasting + texturas por ID -----------------
-    for x in 0..SCREEN_W {
+    for x in 0..view_w {
         // proyección angular: mapea x de pantalla a [-1..1]
-        let camera_x = (2.0 * x as f32 / SCREEN_W as f32) - 1.0;
+        let camera_x = (2.0 * x as f32 / view_w as f32) - 1.0;
         let ray_angle = params.player_angle + (camera_x * (params.fov / 2.0));
 
         let ray_dir_x = ray_angle.cos();
@@ -134,7 +503,10 @@ pub fn draw_frame<'a>(
 
         let mut hit = false;
         let mut side = 0; // 0 = cara X, 1 = cara Y
+        let mut ceil_end = (horizon as i32).clamp(0, view_h); // filas [0, ceil_end) son techo o cielo
         let mut steps = 0;
+        // paredes con huecos que el rayo atraviesa antes de la opaca
+        let mut see_through: Vec<(i32, i32, i32)> = Vec::new(); // (map_x, map_y, side)
 
         // DDA
         loop {
@@ -148,76 +520,40 @@ pub fn draw_frame<'a>(
                 map_y += step_y;
                 side = 1;
             }
-            // cualquier id > 0 es pared
+            // cualquier id > 0 es pared; las “see-through” se anotan y el rayo sigue
             if map.at(map_x, map_y) > 0 {
-                hit = true;
-                break;
+                if !map.is_see_through(map_x, map_y) {
+                    hit = true;
+                    break;
+                }
+                see_through.push((map_x, map_y, side));
             }
             if steps > (map.w + map.h) * 4 { break; } // safety
         }
 
-        if hit {
-            // distancia perpendicular (evita fisheye)
-            let perp_dist = if side == 0 {
-                ((map_x as f32 - params.player_x) + (1 - step_x) as f32 / 2.0) / ray_dir_x
-            } else {
-                ((map_y as f32 - params.player_y) + (1 - step_y) as f32 / 2.0) / ray_dir_y
-            }.abs();
-
-            // Altura proyectada (float)
-            let line_h_f = params.proj_dist / perp_dist;
-
-            // Rango "real" sin recortar
-            let raw_start = (SCREEN_H as f32 / 2.0) - (line_h_f / 2.0);
-            let raw_end   = (SCREEN_H as f32 / 2.0) + (line_h_f / 2.0);
-
-            // Rango visible
-            let vis_start = raw_start.max(0.0) as i32;
-            let vis_end   = raw_end.min((SCREEN_H - 1) as f32) as i32;
+        let ray_dir = (ray_dir_x, ray_dir_y);
+        let step = (step_x, step_y);
 
-            if vis_end >= vis_start {
-                // id de pared y textura
-                let tile_id = map.at(map_x, map_y) as usize; // 1..N
-                let tex = if tile_id == 0 {
-                    &wall_textures[0]
-                } else {
-                    wall_textures.get(tile_id - 1).unwrap_or(&wall_textures[0])
-                };
+        if hit {
+            if let Some(slice) = wall_slice(params, map, wall_textures, (map_x, map_y, side), step, ray_dir, x, horizon) {
+                slice.draw(d);
+                draw_decals_column(d, decals, &slice, view_h);
 
-                // Coordenada “x” en la textura (punto de impacto)
-                let mut wall_x = if side == 0 {
-                    params.player_y + perp_dist * ray_dir_y
-                } else {
-                    params.player_x + perp_dist * ray_dir_x
-                };
-                wall_x -= wall_x.floor();
-
-                let tex_w = tex.width;
-                let tex_h = tex.height;
-                let mut tex_x = (wall_x * tex_w as f32) as i32;
-
-                // Corregir espejo según cara
-                if side == 0 && ray_dir_x > 0.0 { tex_x = tex_w - tex_x - 1; }
-                if side == 1 && ray_dir_y < 0.0 { tex_x = tex_w - tex_x - 1; }
-
-                // Mapeo vertical correcto al clipear
-                let tex_step = tex_h as f32 / line_h_f;
-                let mut tex_y_start = 0.0_f32;
-                if raw_start < 0.0 {
-                    tex_y_start = -raw_start * tex_step;
-                }
-                let visible_px = (vis_end - vis_start + 1) as f32;
-                let src_h = visible_px * tex_step;
+                // Pared de la columna (los disparos buscan aquí su punto de impacto)
+                walls[x as usize] = Some(slice.column());
 
-                let src = Rectangle { x: tex_x as f32, y: tex_y_start, width: 1.0, height: src_h };
-                let dest= Rectangle { x: x as f32,    y: vis_start as f32, width: 1.0, height: visible_px };
+                // zbuffer por columna (sólo paredes opacas: detrás de una rejilla se ve y se dispara)
+                zbuffer[x as usize] = slice.depth;
+                ceil_end = slice.dest.y as i32;
+            }
+        }
 
-                // Sombrear caras Y para profundidad
-                let tint = if side == 1 { Color::GRAY } else { Color::WHITE };
-                d.draw_texture_pro(tex, src, dest, Vector2::new(0.0, 0.0), 0.0, tint);
+        draw_ceiling_column(d, map, params, sky_texture, x, ray_angle, ceil_end, horizon);
 
-                // zbuffer por columna
-                zbuffer[x as usize] = perp_dist;
+        // Las paredes con huecos se pintan luego, mezcladas con los sprites por profundidad
+        for &cell in &see_through {
+            if let Some(slice) = wall_slice(params, map, wall_textures, cell, step, ray_dir, x, horizon) {
+                masked.push(slice);
             }
         }
     }
@@ -241,14 +577,16 @@ for (idx, e) in enemies.iter().enumerate() {
     let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
     if trans_y <= 0.0001 { continue; }
 
-    let sprite_screen_x = (SCREEN_W as f32 / 2.0) * (1.0 + trans_x / trans_y);
-    let sprite_h = (params.proj_dist / trans_y) as i32;
+    let sprite_screen_x = (view_w as f32 / 2.0) * (1.0 + trans_x / trans_y);
+    let scale = params.proj_dist / trans_y;
+    let sprite_h = scale as i32;
     let sprite_w = sprite_h;
 
-    let start_y = (SCREEN_H / 2 - sprite_h / 2).max(0);
-    let end_y   = (SCREEN_H / 2 + sprite_h / 2).min(SCREEN_H - 1);
-    let start_x = ((sprite_screen_x as i32) - sprite_w / 2).max(0);
-    let end_x   = ((sprite_screen_x as i32) + sprite_w / 2).min(SCREEN_W - 1);
+    // De pie en el suelo y alto como una pared (sin recortar: se recorta al dibujar)
+    let end_y   = (horizon + params.eye_z * scale) as i32;
+    let start_y = end_y - sprite_h;
+    let start_x = (sprite_screen_x as i32) - sprite_w / 2;
+    let end_x   = (sprite_screen_x as i32) + sprite_w / 2;
 
     if end_x >= start_x && end_y > start_y {
         // Elegimos visual según estado
@@ -298,21 +636,22 @@ for (idx, e) in enemies.iter().enumerate() {
         let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
         if trans_y <= 0.0001 || trans_y < PK_NEAR { continue; }
 
-        let sprite_screen_x = (SCREEN_W as f32 / 2.0) * (1.0 + trans_x / trans_y);
+        let sprite_screen_x = (view_w as f32 / 2.0) * (1.0 + trans_x / trans_y);
         let phys_h = (params.proj_dist / trans_y) as i32;
 
         let mut sprite_h = (phys_h as f32 * PK_SCALE) as i32;
         let mut sprite_w = sprite_h;
-        let max_h = (SCREEN_H as f32 * PK_MAX_FRAC) as i32;
+        let max_h = (view_h as f32 * PK_MAX_FRAC) as i32;
         sprite_h = sprite_h.clamp(2, max_h);
         sprite_w = sprite_w.clamp(2, max_h);
 
-        let base_y = (SCREEN_H / 2 + phys_h / 2).min(SCREEN_H - 1);
-        let start_y = (base_y - sprite_h).max(0);
+        // apoyado en el suelo (sin recortar: se recorta al dibujar)
+        let base_y = (horizon + params.eye_z * phys_h as f32) as i32;
+        let start_y = base_y - sprite_h;
         let end_y   = base_y;
 
-        let start_x = ((sprite_screen_x as i32) - sprite_w / 2).max(0);
-        let end_x   = ((sprite_screen_x as i32) + sprite_w / 2).min(SCREEN_W - 1);
+        let start_x = (sprite_screen_x as i32) - sprite_w / 2;
+        let end_x   = (sprite_screen_x as i32) + sprite_w / 2;
 
         if end_x >= start_x && end_y > start_y {
             // Selección de visual + glow (para armas)
@@ -339,13 +678,7 @@ for (idx, e) in enemies.iter().enumerate() {
                 ),
                 // Armas: textura por tipo + glow por rareza
                 PickupKind::Weapon { wtype, rarity } => {
-                    let tex_opt = match wtype {
-                        WeaponType::Pistol         => pickup_textures.weapon_pistol,
-                        WeaponType::SMG            => pickup_textures.weapon_smg,
-                        WeaponType::Rifle          => pickup_textures.weapon_rifle,
-                        WeaponType::Shotgun        => pickup_textures.weapon_shotgun,
-                        WeaponType::RocketLauncher => pickup_textures.weapon_rocket,
-                    };
+                    let tex_opt = pickup_textures.weapons.get(wtype.0).copied();
                     let vis = tex_opt
                         .map(SpriteVisual::Texture)
                         .unwrap_or(SpriteVisual::Color(p.color));
@@ -358,6 +691,20 @@ for (idx, e) in enemies.iter().enumerate() {
                         .unwrap_or(SpriteVisual::Color(p.color)),
                     None
                 ),
+                // Cuerpo a cuerpo
+                PickupKind::Item { item: Item::Melee(m), .. } => {
+                    let tex_opt = match m {
+                        MeleeType::Knife => pickup_textures.knife,
+                        MeleeType::Bat   => pickup_textures.bat,
+                        MeleeType::Fist  => None,
+                    };
+                    (tex_opt.map(SpriteVisual::Texture).unwrap_or(SpriteVisual::Color(p.color)), None)
+                }
+                PickupKind::Item { item: Item::Consumable(ConsumableType::Grenade), .. } => (
+                    pickup_textures.grenade.map(SpriteVisual::Texture)
+                        .unwrap_or(SpriteVisual::Color(p.color)),
+                    None
+                ),
                 // otros (Item genérico…)
                 _ => (SpriteVisual::Color(p.color), None),
             };
@@ -384,7 +731,7 @@ for (idx, e) in enemies.iter().enumerate() {
         let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
         if trans_y <= 0.0001 { continue; }
 
-        let sprite_screen_x = (SCREEN_W as f32 / 2.0) * (1.0 + trans_x / trans_y);
+        let sprite_screen_x = (view_w as f32 / 2.0) * (1.0 + trans_x / trans_y);
         let phys_h = (params.proj_dist / trans_y) as i32;
 
         let scale = match deco.kind {
@@ -394,16 +741,17 @@ for (idx, e) in enemies.iter().enumerate() {
 
         let mut sprite_h = (phys_h as f32 * scale) as i32;
         let mut sprite_w = sprite_h;
-        let max_h = (SCREEN_H as f32 * 0.35) as i32;
+        let max_h = (view_h as f32 * 0.35) as i32;
         sprite_h = sprite_h.clamp(2, max_h);
         sprite_w = sprite_w.clamp(2, max_h);
 
-        let base_y = (SCREEN_H / 2 + phys_h / 2).min(SCREEN_H - 1);
-        let start_y = (base_y - sprite_h).max(0);
+        // apoyado en el suelo (sin recortar: se recorta al dibujar)
+        let base_y = (horizon + params.eye_z * phys_h as f32) as i32;
+        let start_y = base_y - sprite_h;
         let end_y   = base_y;
 
-        let start_x = ((sprite_screen_x as i32) - sprite_w / 2).max(0);
-        let end_x   = ((sprite_screen_x as i32) + sprite_w / 2).min(SCREEN_W - 1);
+        let start_x = (sprite_screen_x as i32) - sprite_w / 2;
+        let end_x   = (sprite_screen_x as i32) + sprite_w / 2;
 
         let color = match deco.kind {
             DecoKind::Blocking => Color::BROWN,
@@ -430,21 +778,22 @@ for (idx, e) in enemies.iter().enumerate() {
         let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
         if trans_y <= 0.0001 { continue; }
 
-        let sprite_screen_x = (SCREEN_W as f32 / 2.0) * (1.0 + trans_x / trans_y);
+        let sprite_screen_x = (view_w as f32 / 2.0) * (1.0 + trans_x / trans_y);
         let phys_h = (params.proj_dist / trans_y) as i32;
 
         let mut sprite_h = (phys_h as f32 * 0.55) as i32;
         let mut sprite_w = sprite_h;
-        let max_h = (SCREEN_H as f32 * 0.30) as i32;
+        let max_h = (view_h as f32 * 0.30) as i32;
         sprite_h = sprite_h.clamp(2, max_h);
         sprite_w = sprite_w.clamp(2, max_h);
 
-        let base_y = (SCREEN_H / 2 + phys_h / 2).min(SCREEN_H - 1);
-        let start_y = (base_y - sprite_h).max(0);
+        // apoyado en el suelo (sin recortar: se recorta al dibujar)
+        let base_y = (horizon + params.eye_z * phys_h as f32) as i32;
+        let start_y = base_y - sprite_h;
         let end_y   = base_y;
 
-        let start_x = ((sprite_screen_x as i32) - sprite_w / 2).max(0);
-        let end_x   = ((sprite_screen_x as i32) + sprite_w / 2).min(SCREEN_W - 1);
+        let start_x = (sprite_screen_x as i32) - sprite_w / 2;
+        let end_x   = (sprite_screen_x as i32) + sprite_w / 2;
 
         // textura por estado
         let tex_opt = if c.opened {
@@ -469,17 +818,157 @@ for (idx, e) in enemies.iter().enumerate() {
         }
     }
 
+    // ---- Checkpoints: mástil fino + bandera arriba (gris / verde si es el activo) ----
+    for cp in checkpoints {
+        let rel_x = cp.x - params.player_x;
+        let rel_y = cp.y - params.player_y;
+
+        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+        let trans_x = inv_det * (dir_y * rel_x - dir_x * rel_y);
+        let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
+        if trans_y <= 0.05 { continue; }
+
+        let sprite_screen_x = ((view_w as f32 / 2.0) * (1.0 + trans_x / trans_y)) as i32;
+        let scale = params.proj_dist / trans_y;
+        let y_at = |z: f32| (horizon - (z - params.eye_z) * scale) as i32;
+
+        let pole_w = ((0.04 * scale) as i32).max(1);
+        let flag_w = ((0.30 * scale) as i32).max(2);
+        let flag_color = if cp.active { Color::new(60, 220, 90, 255) } else { Color::new(150, 150, 150, 255) };
+
+        sprites.push(QueuedSprite {
+            start_x: sprite_screen_x - pole_w / 2, end_x: sprite_screen_x + pole_w / 2,
+            start_y: y_at(0.8), end_y: y_at(0.0),
+            depth: trans_y,
+            visual: SpriteVisual::Color(Color::new(200, 200, 200, 255)),
+            glow: None,
+            enemy_idx: None,
+        });
+        sprites.push(QueuedSprite {
+            start_x: sprite_screen_x, end_x: sprite_screen_x + flag_w,
+            start_y: y_at(0.8), end_y: y_at(0.6),
+            depth: trans_y - 0.001, // delante del mástil
+            visual: SpriteVisual::Color(flag_color),
+            glow: None,
+            enemy_idx: None,
+        });
+    }
+
+    // ---- Cohetes: cuerpo gris con el motor encendido delante ----
+    for r in projectiles.iter() {
+        let rel_x = r.x - params.player_x;
+        let rel_y = r.y - params.player_y;
+
+        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+        let trans_x = inv_det * (dir_y * rel_x - dir_x * rel_y);
+        let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
+        if trans_y <= 0.05 { continue; }
+
+        let sprite_screen_x = ((view_w as f32 / 2.0) * (1.0 + trans_x / trans_y)) as i32;
+        let scale = params.proj_dist / trans_y;
+        let center_y = (horizon - (r.z - params.eye_z) * scale) as i32;
+
+        let body = ((0.10 * scale) as i32).max(2);
+        let flame = ((0.05 * scale) as i32).max(1);
+        sprites.push(QueuedSprite {
+            start_x: sprite_screen_x - body / 2, end_x: sprite_screen_x + body / 2,
+            start_y: center_y - body / 2, end_y: center_y + body / 2,
+            depth: trans_y,
+            visual: SpriteVisual::Color(Color::new(70, 70, 75, 255)),
+            glow: None,
+            enemy_idx: None,
+        });
+        sprites.push(QueuedSprite {
+            start_x: sprite_screen_x - flame / 2, end_x: sprite_screen_x + flame / 2,
+            start_y: center_y - flame / 2, end_y: center_y + flame / 2,
+            depth: trans_y - 0.001, // delante del cuerpo
+            visual: SpriteVisual::Color(Color::new(255, 190, 60, 255)),
+            glow: None,
+            enemy_idx: None,
+        });
+    }
+
+    // ---- Granadas: el mismo sprite del pickup, pequeño y a su altura ----
+    for g in projectiles.grenades() {
+        let rel_x = g.x - params.player_x;
+        let rel_y = g.y - params.player_y;
+
+        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+        let trans_x = inv_det * (dir_y * rel_x - dir_x * rel_y);
+        let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
+        if trans_y <= 0.05 { continue; }
+
+        let sprite_screen_x = ((view_w as f32 / 2.0) * (1.0 + trans_x / trans_y)) as i32;
+        let scale = params.proj_dist / trans_y;
+        let center_y = (horizon - (g.z - params.eye_z) * scale) as i32;
+
+        let size = ((GRENADE_RADIUS * 3.0 * scale) as i32).max(2);
+        sprites.push(QueuedSprite {
+            start_x: sprite_screen_x - size / 2, end_x: sprite_screen_x + size / 2,
+            start_y: center_y - size / 2, end_y: center_y + size / 2,
+            depth: trans_y,
+            visual: pickup_textures.grenade.map(SpriteVisual::Texture)
+                .unwrap_or(SpriteVisual::Color(GRENADE_COLOR)),
+            glow: None,
+            enemy_idx: None,
+        });
+    }
+
+    // ---- Partículas (cuadraditos de color, misma prueba de zbuffer) ----
+    for p in particles.iter() {
+        let rel_x = p.x - params.player_x;
+        let rel_y = p.y - params.player_y;
+
+        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+        let trans_x = inv_det * (dir_y * rel_x - dir_x * rel_y);
+        let trans_y = inv_det * (-plane_y * rel_x + plane_x * rel_y);
+        if trans_y <= 0.05 { continue; }
+
+        let sprite_screen_x = (view_w as f32 / 2.0) * (1.0 + trans_x / trans_y);
+        let scale = params.proj_dist / trans_y;
+        let size = ((p.size() * scale) as i32).max(1);
+        let center_y = (horizon - (p.z - params.eye_z) * scale) as i32;
+
+        let start_x = (sprite_screen_x as i32) - size / 2;
+        let end_x   = (sprite_screen_x as i32) + size / 2;
+        let start_y = center_y - size / 2;
+        let end_y   = center_y + size / 2;
+
+        if end_x >= start_x && end_y >= start_y {
+            sprites.push(QueuedSprite {
+                start_x, end_x, start_y, end_y, depth: trans_y,
+                visual: SpriteVisual::Color(p.color()),
+                glow: None,
+                enemy_idx: None,
+            });
+        }
+    }
+
     // Orden de pintado: lejos → cerca (para zbuffer correcto)
     sprites.sort_by(|a, b| b.depth.partial_cmp(&a.depth).unwrap_or(std::cmp::Ordering::Equal));
 
+    // Paredes see-through en el mismo orden lejos → cerca
+    masked.sort_by(|a, b| b.depth.partial_cmp(&a.depth).unwrap_or(std::cmp::Ordering::Equal));
+    let mut masked_iter = masked.iter().peekable();
+
     // Dibujar sprites con zbuffer
     for s in &sprites {
+        // primero las rejillas que quedan detrás de este sprite
+        while let Some(w) = masked_iter.next_if(|w| w.depth > s.depth) {
+            w.draw(d);
+        }
+
+        // Rango visible (los sprites se guardan sin recortar)
+        let (x0, x1) = (s.start_x.max(0), s.end_x.min(view_w - 1));
+        let (y0, y1) = (s.start_y.max(0), s.end_y.min(view_h - 1));
+        if x1 < x0 || y1 < y0 { continue; }
+
         match s.visual {
             SpriteVisual::Color(col) => {
-                for stripe in s.start_x..=s.end_x {
+                for stripe in x0..=x1 {
                     let x = stripe as usize;
                     if x < zbuffer.len() && s.depth < zbuffer[x] {
-                        d.draw_line(stripe, s.start_y, stripe, s.end_y, col);
+                        d.draw_line(stripe, y0, stripe, y1, col);
                     }
                 }
             }
@@ -487,16 +976,21 @@ for (idx, e) in enemies.iter().enumerate() {
                 let tex_w = tex.width as f32;
                 let tex_h = tex.height as f32;
                 let sprite_w = (s.end_x - s.start_x + 1) as f32;
-                let visible_h = (s.end_y - s.start_y + 1) as f32;
+                let sprite_h = (s.end_y - s.start_y + 1) as f32;
+
+                // recorte vertical → parte de la textura que queda en pantalla
+                let src_y = (y0 - s.start_y) as f32 / sprite_h * tex_h;
+                let visible_h = (y1 - y0 + 1) as f32;
+                let src_h = visible_h / sprite_h * tex_h;
 
-                for stripe in s.start_x..=s.end_x {
+                for stripe in x0..=x1 {
                     let x = stripe as usize;
                     if x >= zbuffer.len() || !(s.depth < zbuffer[x]) { continue; }
 
                     // 🔆 GLOW (debajo de la textura), ligeramente más alto
                     if let Some(glow) = s.glow {
                         let gy1 = (s.start_y - 2).max(0);
-                        let gy2 = (s.end_y + 2).min(SCREEN_H - 1);
+                        let gy2 = (s.end_y + 2).min(view_h - 1);
                         d.draw_line(stripe, gy1, stripe, gy2, glow);
                     }
 
@@ -504,10 +998,10 @@ for (idx, e) in enemies.iter().enumerate() {
                     let u = (stripe - s.start_x) as f32 / sprite_w;
                     let tex_x = (u * (tex_w - 1.0)).clamp(0.0, tex_w - 1.0);
 
-                    let src = Rectangle { x: tex_x, y: 0.0, width: 1.0, height: tex_h };
+                    let src = Rectangle { x: tex_x, y: src_y, width: 1.0, height: src_h };
                     let dest= Rectangle {
                         x: stripe as f32,
-                        y: s.start_y as f32,
+                        y: y0 as f32,
                         width: 1.0,
                         height: visible_h,
                     };
@@ -518,17 +1012,24 @@ for (idx, e) in enemies.iter().enumerate() {
         }
     }
 
+    // rejillas más cercanas que cualquier sprite
+    for w in masked_iter {
+        w.draw(d);
+    }
+
     // Rangos de enemigos para hitscan
     for s in &sprites {
         if let Some(idx) = s.enemy_idx {
             drawn.push(DrawnSprite {
                 start_x: s.start_x,
                 end_x: s.end_x,
+                start_y: s.start_y,
+                end_y: s.end_y,
                 depth: s.depth,
                 idx,
             });
         }
     }
 
-    RenderOutput { zbuffer, drawn }
+    RenderOutput { zbuffer, drawn, walls }
 }
@@ -0,0 +1,142 @@
+use raylib::prelude::*;
+use crate::types::Viewport;
+
+// Duraciones (s) de cada efecto
+const DAMAGE_FADE: f32 = 0.9;   // viñeta roja: de 1.0 a 0 en este tiempo
+const SHIELD_FLASH: f32 = 0.25; // flash azul
+const ARC_TIME: f32 = 1.2;      // arco de dirección de daño
+const HIT_MARKER: f32 = 0.15;   // marca de impacto en la mira
+const PICKUP_FLASH: f32 = 0.3;  // flash de color al recoger algo
+
+/// Arco hacia el atacante (ángulo en el mundo, tiempo restante)
+struct DamageArc { angle: f32, t: f32 }
+
+/// Capa de efectos de pantalla que se pinta encima del HUD:
+/// viñeta roja por daño, flash azul del escudo, arcos de dirección de daño,
+/// marca de impacto en la mira y flash al recoger pickups.
+pub struct ScreenFx {
+    damage: f32,                  // intensidad de la viñeta (0..1)
+    shield: f32,                  // tiempo restante del flash azul
+    arcs: Vec<DamageArc>,
+    hit_marker: f32,              // tiempo restante
+    headshot: bool,               // la última marca fue a la cabeza (roja y más grande)
+    pickup: Option<(Color, f32)>, // (color, tiempo restante)
+}
+
+impl ScreenFx {
+    pub fn new() -> Self {
+        Self { damage: 0.0, shield: 0.0, arcs: Vec::new(), hit_marker: 0.0, headshot: false, pickup: None }
+    }
+
+    /// El jugador recibió daño. `to_attacker` = ángulo en el mundo desde el jugador hacia quien lo hirió.
+    pub fn on_player_damage(&mut self, hp_lost: i32, shield_lost: i32, to_attacker: f32) {
+        if hp_lost > 0 {
+            // ~40 de vida perdidos = viñeta al máximo
+            self.damage = (self.damage + hp_lost as f32 / 40.0).min(1.0);
+        }
+        if shield_lost > 0 {
+            self.shield = SHIELD_FLASH;
+        }
+        if hp_lost > 0 || shield_lost > 0 {
+            // daño continuo (melee) desde la misma dirección → refresca el arco existente
+            let near = self.arcs.iter_mut().find(|a| angle_diff(a.angle, to_attacker).abs() < 0.35);
+            match near {
+                Some(a) => { a.angle = to_attacker; a.t = ARC_TIME; }
+                None => {
+                    if self.arcs.len() >= 8 { self.arcs.remove(0); }
+                    self.arcs.push(DamageArc { angle: to_attacker, t: ARC_TIME });
+                }
+            }
+        }
+    }
+
+    /// El hitscan del jugador dio a un enemigo (`headshot` = alguna bala entró por la cabeza)
+    pub fn on_hit(&mut self, headshot: bool) {
+        self.hit_marker = HIT_MARKER;
+        self.headshot = headshot;
+    }
+
+    pub fn on_pickup(&mut self, color: Color) {
+        self.pickup = Some((color, PICKUP_FLASH));
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.damage = (self.damage - dt / DAMAGE_FADE).max(0.0);
+        self.shield = (self.shield - dt).max(0.0);
+        self.hit_marker = (self.hit_marker - dt).max(0.0);
+        for a in self.arcs.iter_mut() { a.t -= dt; }
+        self.arcs.retain(|a| a.t > 0.0);
+        if let Some((_, t)) = &mut self.pickup {
+            *t -= dt;
+            if *t <= 0.0 { self.pickup = None; }
+        }
+    }
+
+    pub fn draw(&self, d: &mut RaylibDrawHandle, vp: Viewport, player_angle: f32) {
+        let (w, h) = (vp.w, vp.h);
+
+        // Flash de pickup (tinte plano)
+        if let Some((col, t)) = self.pickup {
+            let a = (70.0 * t / PICKUP_FLASH) as u8;
+            d.draw_rectangle(0, 0, w, h, Color::new(col.r, col.g, col.b, a));
+        }
+
+        // Flash azul del escudo
+        if self.shield > 0.0 {
+            let a = (90.0 * self.shield / SHIELD_FLASH) as u8;
+            draw_vignette(d, vp, Color::new(60, 140, 255, a), 0.18);
+        }
+
+        // Viñeta roja
+        if self.damage > 0.0 {
+            let a = (200.0 * self.damage) as u8;
+            draw_vignette(d, vp, Color::new(200, 0, 0, a), 0.12 + 0.12 * self.damage);
+        }
+
+        // Arcos de dirección: adelante = arriba, ángulos positivos a la derecha
+        let center = Vector2::new(w as f32 / 2.0, h as f32 / 2.0);
+        let r = h as f32 * 0.22;
+        for a in &self.arcs {
+            let rel = angle_diff(a.angle, player_angle).to_degrees() - 90.0;
+            let alpha = (220.0 * (a.t / ARC_TIME).min(1.0)) as u8;
+            d.draw_ring(center, r, r + 10.0, rel - 25.0, rel + 25.0, 16, Color::new(230, 30, 30, alpha));
+        }
+
+        // Marca de impacto: 4 diagonales alrededor de la mira
+        if self.hit_marker > 0.0 {
+            let a = (255.0 * self.hit_marker / HIT_MARKER) as u8;
+            // a la cabeza: marca roja, más larga y gruesa
+            let (col, len, thick) = if self.headshot {
+                (Color::new(255, 60, 40, a), 17, 3.0)
+            } else {
+                (Color::new(255, 255, 255, a), 12, 2.0)
+            };
+            let (cx, cy) = (w / 2, h / 2);
+            for (sx, sy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+                d.draw_line_ex(
+                    Vector2::new((cx + sx * 5) as f32, (cy + sy * 5) as f32),
+                    Vector2::new((cx + sx * len) as f32, (cy + sy * len) as f32),
+                    thick, col,
+                );
+            }
+        }
+    }
+}
+
+/// Diferencia angular en (-π, π]
+fn angle_diff(a: f32, b: f32) -> f32 {
+    let tau = std::f32::consts::TAU;
+    let d = (a - b).rem_euclid(tau);
+    if d > std::f32::consts::PI { d - tau } else { d }
+}
+
+/// Bordes con degradado de `col` a transparente; `frac` = grosor relativo al lado menor
+fn draw_vignette(d: &mut RaylibDrawHandle, vp: Viewport, col: Color, frac: f32) {
+    let (w, h) = (vp.w, vp.h);
+    let t = (w.min(h) as f32 * frac) as i32;
+    let clear = Color::new(col.r, col.g, col.b, 0);
+    d.draw_rectangle_gradient_v(0, 0, w, t, col, clear);
+    d.draw_rectangle_gradient_v(0, h - t, w, t, clear, col);
+    d.draw_rectangle_gradient_h(0, 0, t, h, col, clear);
+    d.draw_rectangle_gradient_h(w - t, 0, t, h, clear, col);
+}
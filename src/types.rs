@@ -1,8 +1,25 @@
 use raylib::prelude::*;
+use crate::consts::{
+    CROUCH_SPREAD_MULT, HEAD_DAMAGE_MULT, TORSO_DAMAGE_MULT, LEGS_DAMAGE_MULT,
+    PUNCH_DAMAGE, PUNCH_RANGE, PUNCH_COOLDOWN, PUNCH_ARC,
+    KNIFE_DAMAGE, KNIFE_RANGE, KNIFE_SWING, KNIFE_ARC,
+    BAT_DAMAGE, BAT_RANGE, BAT_SWING, BAT_ARC, BAT_KNOCKBACK,
+};
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum EnemyState { Idle, Chase, Dead }
 
+/// Tamaño actual de la ventana en píxeles (cambia al redimensionar o
+/// al pasar a pantalla completa). El HUD se ancla a esto cada frame.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Viewport { pub w: i32, pub h: i32 }
+
+impl Viewport {
+    pub fn of(rl: &RaylibHandle) -> Self {
+        Self { w: rl.get_screen_width().max(1), h: rl.get_screen_height().max(1) }
+    }
+}
+
 // ── Munición ─────────────────────────────────────────────
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum AmmoType {
@@ -84,26 +101,9 @@ impl Rarity {
     }
 }
 
+/// Índice del arma en el catálogo (`assets/weapons.txt`, ver `weapons.rs`)
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub enum WeaponType {
-    Pistol,
-    SMG,
-    Rifle,
-    Shotgun,
-    RocketLauncher,
-}
-
-impl WeaponType {
-    pub fn name(self) -> &'static str {
-        match self {
-            WeaponType::Pistol => "Pistola",
-            WeaponType::SMG => "SMG",
-            WeaponType::Rifle => "Rifle",
-            WeaponType::Shotgun => "Escopeta",
-            WeaponType::RocketLauncher => "Lanzacohetes",
-        }
-    }
-}
+pub struct WeaponType(pub usize);
 
 #[derive(Clone, Copy)]
 pub struct Weapon {
@@ -115,6 +115,38 @@ pub struct Weapon {
     pub reload_time: f32,       // recarga BASE
     pub ammo_type: AmmoType,
     pub rarity: Rarity,         // <- rareza
+    pub pellets: u32,           // rayos por disparo (escopeta > 1); `damage` es por perdigón
+    pub spread: f32,            // semiángulo del cono de dispersión (rad) quieto y sin haber disparado
+    pub bloom: f32,             // lo que se abre el cono con cada disparo (rad, se suma a `WeaponState::bloom`)
+    pub bloom_max: f32,         // tope de la apertura por disparos seguidos (rad)
+    pub move_spread: f32,       // apertura extra moviéndose a velocidad normal (rad)
+    pub short: &'static str,    // etiqueta corta para los slots del HUD
+    pub recoil: f32,            // patada visual de cada disparo (se suma a `WeaponState::kick`, tope 1.0)
+    pub move_mult: f32,         // multiplicador de velocidad al empuñarla (armas pesadas frenan)
+    pub draw_time: f32,         // tiempo (s) para sacarla al cambiar de slot; no dispara mientras tanto
+    pub spin_up: f32,           // tiempo (s) que tarda en girar el cañón antes de disparar (0 = al instante)
+    pub projectile: bool,       // dispara un proyectil con tiempo de vuelo en vez de un hitscan
+    pub fire_modes: &'static [FireMode], // modos que admite; el primero es el de fábrica
+    pub burst: u32,             // balas por ráfaga
+    pub burst_interval: f32,    // tiempo (s) entre balas dentro de una ráfaga
+}
+
+/// Cómo responde el arma al gatillo
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FireMode {
+    Semi,  // una bala por pulsación
+    Burst, // `Weapon::burst` balas por pulsación (la ráfaga termina aunque se suelte)
+    Auto,  // dispara mientras se mantenga apretado
+}
+
+impl FireMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            FireMode::Semi  => "SEMI",
+            FireMode::Burst => "RÁFAGA",
+            FireMode::Auto  => "AUTO",
+        }
+    }
 }
 
 impl Weapon {
@@ -124,8 +156,24 @@ impl Weapon {
     pub fn effective_reload(&self) -> f32 {
         self.reload_time * self.rarity.reload_mult()
     }
+    /// Semiángulo (rad) del cono en el que caen las balas ahora mismo.
+    /// `moving` = `Player::bob_amp` (0 quieto .. 1.5 sprint), `crouch` = 0 de pie .. 1 agachado
+    pub fn cone(&self, bloom: f32, moving: f32, crouch: f32) -> f32 {
+        let c = self.spread + bloom + self.move_spread * moving;
+        c * (1.0 - (1.0 - CROUCH_SPREAD_MULT) * crouch)
+    }
+    /// Modo siguiente al cambiar con la tecla de modo de disparo (vuelve al primero al final)
+    pub fn next_mode(&self, mode: FireMode) -> FireMode {
+        let i = self.fire_modes.iter().position(|&m| m == mode).unwrap_or(0);
+        self.fire_modes[(i + 1) % self.fire_modes.len()]
+    }
+    /// Daño base de un disparo completo (todos los perdigones dentro)
+    pub fn shot_damage(&self) -> i32 {
+        self.damage * self.pellets as i32
+    }
 }
 
+#[derive(Clone)]
 pub struct Enemy {
     pub x: f32, pub y: f32,
     pub hp: i32,
@@ -136,9 +184,39 @@ pub struct Enemy {
     pub weapon_cd: f32,
     pub flash_timer: f32,
     pub death_anim_t: f32,
+    pub knock: (f32, f32), // empuje de explosiones (celdas/s), se frena solo
+    pub zones: HitZones,   // dónde tiene la cabeza/piernas dentro de su sprite
 }
 impl Enemy { pub fn is_alive(&self) -> bool { self.state != EnemyState::Dead && self.hp > 0 } }
 
+/// Zona del cuerpo donde entra una bala
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitZone { Head, Torso, Legs }
+
+impl HitZone {
+    pub fn damage_mult(self) -> f32 {
+        match self {
+            HitZone::Head  => HEAD_DAMAGE_MULT,
+            HitZone::Torso => TORSO_DAMAGE_MULT,
+            HitZone::Legs  => LEGS_DAMAGE_MULT,
+        }
+    }
+}
+
+/// Cortes verticales del sprite de un enemigo, como fracción de su alto (0 = arriba, 1 = abajo):
+/// por encima de `head` es cabeza, por debajo de `legs` son piernas y lo de en medio, torso
+#[derive(Clone, Copy, Debug)]
+pub struct HitZones { pub head: f32, pub legs: f32 }
+
+impl HitZones {
+    /// Medido sobre `assets/enemies/enemy_alive.png` (el cuello cae a ~29%, la cintura a ~57%)
+    pub const SOLDIER: HitZones = HitZones { head: 0.29, legs: 0.57 };
+
+    pub fn zone_at(&self, frac: f32) -> HitZone {
+        if frac < self.head { HitZone::Head } else if frac < self.legs { HitZone::Torso } else { HitZone::Legs }
+    }
+}
+
 // ── Pickups ─────────────────────────────────────────────
 #[derive(Clone, Copy)]
 pub enum PickupKind {
@@ -155,6 +233,7 @@ pub enum PickupKind {
     Item { item: Item, count: i32 },
 }
 
+#[derive(Clone)]
 pub struct Pickup {
     pub x: f32,
     pub y: f32,
@@ -164,6 +243,16 @@ pub struct Pickup {
     pub spawn_lock: f32, // para evitar recoger en el mismo frame del spawn
 }
 
+/// Modificador de velocidad de movimiento (estado, terreno, arma, consumible…).
+/// Hay uno como mucho por `id`; el efecto total es el producto de todos.
+#[derive(Clone, Copy)]
+pub struct MoveMod {
+    pub id: &'static str,
+    pub mult: f32,
+    pub time_left: Option<f32>, // None = hasta que se quite a mano
+}
+
+#[derive(Clone)]
 pub struct Player {
     pub x: f32, pub y: f32, pub angle: f32,
     pub hp: i32,
@@ -176,13 +265,44 @@ pub struct Player {
     pub slots: [Option<SlotItem>; 5],
     pub selected: Option<usize>,
 
-    pub punch_cd: f32,
+    pub melee_cd: f32, // tiempo hasta poder volver a golpear (puño, cuchillo, bate o golpe rápido)
+    pub grenade_charge: f32, // carga del lanzamiento de granada (0 = sin cargar .. 1 = tiro máximo)
+
+    // Animación del arma empuñada
+    pub bob_t: f32,   // fase del balanceo al caminar
+    pub bob_amp: f32, // intensidad del balanceo (0 quieto .. 1 a velocidad normal)
+    pub draw_t: f32,  // tiempo restante sacando el arma tras cambiar de slot
+
+    // Vista vertical: pitch (y-shearing) y altura de ojos (agacharse/saltar)
+    pub pitch: f32,  // desplazamiento del horizonte en fracción del alto de la vista
+    pub eye_z: f32,  // altura actual de los ojos (0 = suelo, 1 = techo)
+    pub jump_z: f32, // altura del salto sobre el suelo
+    pub vz: f32,     // velocidad vertical del salto
+    pub crouch: f32, // 0 = de pie .. 1 = agachado (interpolado)
+
+    // Sprint / estamina y modificadores de velocidad
+    pub stamina: f32,
+    pub stamina_cd: f32,         // espera antes de recuperar estamina
+    pub sprinting: bool,
+    pub move_mods: Vec<MoveMod>, // se multiplican entre sí
+
+    pub knock: (f32, f32), // empuje de explosiones (celdas/s), se frena solo
 
 }
 
 
+/// Rango en pantalla (sin recortar) de un enemigo dibujado, para hitscan
 #[derive(Clone, Copy)]
-pub struct DrawnSprite { pub start_x: i32, pub end_x: i32, pub depth: f32, pub idx: usize }
+pub struct DrawnSprite { pub start_x: i32, pub end_x: i32, pub start_y: i32, pub end_y: i32, pub depth: f32, pub idx: usize }
+
+/// Cara de una pared: celda + normal (apunta hacia el lado desde donde se ve)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WallFace { pub cell_x: i32, pub cell_y: i32, pub normal: (i32, i32) }
+
+/// Impacto de un rayo en una pared. (u, v) en espacio de textura:
+/// u ∈ [0,1) a lo largo de la cara, v ∈ [0,1] de arriba (0) a abajo (1).
+#[derive(Clone, Copy, Debug)]
+pub struct WallHit { pub face: WallFace, pub u: f32, pub v: f32 }
 
 // ── Decoraciones y cofres (ya los tienes) ─────────────────
 #[derive(Clone, Copy)]
@@ -199,6 +319,9 @@ pub struct Chest {
 }
 impl Chest { pub fn is_blocking(&self) -> bool { true } }
 
+/// Punto de control del mapa ('X'); `active` = el último tocado (donde se reaparece)
+pub struct Checkpoint { pub x: f32, pub y: f32, pub active: bool }
+
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum ConsumableType {
@@ -206,6 +329,7 @@ pub enum ConsumableType {
     HealthBig,   // cura 100 (cap 100)
     ShieldSmall, // +25 hasta 50
     ShieldBig,   // +50 (cap 100)
+    Grenade,     // se lanza manteniendo el disparo (ver projectiles.rs)
 }
 
 impl ConsumableType {
@@ -215,6 +339,34 @@ impl ConsumableType {
             ConsumableType::HealthBig   => "Vida mayor",
             ConsumableType::ShieldSmall => "Escudo menor",
             ConsumableType::ShieldBig   => "Escudo mayor",
+            ConsumableType::Grenade     => "Granada",
+        }
+    }
+}
+
+/// Arma cuerpo a cuerpo. `Fist` es el puño (mano vacía / golpe rápido con un arma de fuego);
+/// las demás van en un slot como cualquier ítem
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MeleeType { Fist, Knife, Bat }
+
+/// Stats de un golpe: daño a cada enemigo dentro del arco, tiempo entre golpes (s),
+/// ancho del arco delante del jugador (rad), alcance (celdas) y empuje (celdas/s)
+#[derive(Clone, Copy, Debug)]
+pub struct MeleeStats { pub damage: i32, pub swing: f32, pub arc: f32, pub range: f32, pub knockback: f32 }
+
+impl MeleeType {
+    pub fn name(self) -> &'static str {
+        match self {
+            MeleeType::Fist  => "Puño",
+            MeleeType::Knife => "Cuchillo",
+            MeleeType::Bat   => "Bate",
+        }
+    }
+    pub fn stats(self) -> MeleeStats {
+        match self {
+            MeleeType::Fist  => MeleeStats { damage: PUNCH_DAMAGE, swing: PUNCH_COOLDOWN, arc: PUNCH_ARC, range: PUNCH_RANGE, knockback: 0.0 },
+            MeleeType::Knife => MeleeStats { damage: KNIFE_DAMAGE, swing: KNIFE_SWING,    arc: KNIFE_ARC, range: KNIFE_RANGE, knockback: 0.0 },
+            MeleeType::Bat   => MeleeStats { damage: BAT_DAMAGE,   swing: BAT_SWING,      arc: BAT_ARC,   range: BAT_RANGE,   knockback: BAT_KNOCKBACK },
         }
     }
 }
@@ -226,6 +378,23 @@ pub struct WeaponState {
     pub weapon_cd: f32,
     pub reloading: bool,
     pub reload_cd: f32,
+    pub kick: f32, // retroceso visual del arma empuñada (0..1), decae solo
+    pub spin: f32, // giro del cañón (0..1); las de `Weapon::spin_up` > 0 sólo disparan a 1
+    pub bloom: f32, // apertura del cono por disparos seguidos (rad), vuelve a 0 con BLOOM_RECOVERY
+    pub mode: FireMode,      // modo de disparo elegido
+    pub burst_left: u32,     // balas que le quedan a la ráfaga en curso
+    pub trigger_held: bool,  // semi/ráfaga ya disparó con esta pulsación: hay que soltar el gatillo
+}
+
+impl WeaponState {
+    /// Recién cogida: cargador lleno y en su modo de fábrica
+    pub fn new(w: &Weapon) -> Self {
+        Self {
+            ammo_in_mag: w.mag_size, weapon_cd: 0.0, reloading: false, reload_cd: 0.0,
+            kick: 0.0, spin: 0.0, bloom: 0.0,
+            mode: w.fire_modes[0], burst_left: 0, trigger_held: false,
+        }
+    }
 }
 
 /// Un ítem que puede ir en un slot: arma (con su estado) o consumible
@@ -233,6 +402,7 @@ pub struct WeaponState {
 pub enum Item {
     Weapon(Weapon, WeaponState),
     Consumable(ConsumableType),
+    Melee(MeleeType),
 }
 
 /// Slot (1 de 5)
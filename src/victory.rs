@@ -1,6 +1,7 @@
 use raylib::prelude::*;
-use raylib::consts::{MouseButton, KeyboardKey};
-use crate::consts::{SCREEN_W, SCREEN_H};
+use raylib::consts::MouseButton;
+use crate::types::Viewport;
+use crate::input::{Action, Bindings, CHOICE_HELP};
 
 /// Qué hacer desde la pantalla de victoria
 pub enum VictoryAction {
@@ -12,9 +13,11 @@ pub enum VictoryAction {
 pub struct VictoryScreen {
     hovered_menu: bool,
     hovered_restart: bool,
+    restart_focused: bool, // foco de teclado/mando (←/→ lo mueven)
     // stats que mostramos
     elapsed_secs: f32,
     kills: u32,
+    headshots: u32,
 }
 
 impl VictoryScreen {
@@ -22,43 +25,53 @@ impl VictoryScreen {
         Self {
             hovered_menu: false,
             hovered_restart: false,
+            restart_focused: true,
             elapsed_secs: 0.0,
             kills: 0,
+            headshots: 0,
         }
     }
 
-    pub fn set_stats(&mut self, elapsed_secs: f32, kills: u32) {
+    pub fn set_stats(&mut self, elapsed_secs: f32, kills: u32, headshots: u32) {
         self.elapsed_secs = elapsed_secs.max(0.0);
         self.kills = kills;
+        self.headshots = headshots;
     }
 
-    fn btn_menu_rect(&self) -> (i32, i32, i32, i32) {
+    fn btn_menu_rect(&self, vp: Viewport) -> (i32, i32, i32, i32) {
         let w = 240; let h = 56;
-        let x = (SCREEN_W / 2) - w - 12; // a la izquierda del centro
-        let y = (SCREEN_H * 2 / 3) - h/2;
+        let x = (vp.w / 2) - w - 12; // a la izquierda del centro
+        let y = (vp.h * 2 / 3) - h/2;
         (x, y, w, h)
     }
-    fn btn_restart_rect(&self) -> (i32, i32, i32, i32) {
+    fn btn_restart_rect(&self, vp: Viewport) -> (i32, i32, i32, i32) {
         let w = 240; let h = 56;
-        let x = (SCREEN_W / 2) + 12; // a la derecha del centro
-        let y = (SCREEN_H * 2 / 3) - h/2;
+        let x = (vp.w / 2) + 12; // a la derecha del centro
+        let y = (vp.h * 2 / 3) - h/2;
         (x, y, w, h)
     }
 
     /// Manejar entrada (teclas/ratón). Llamar **antes** del begin_drawing.
-    pub fn handle_input(&mut self, rl: &RaylibHandle) -> VictoryAction {
+    pub fn handle_input(&mut self, rl: &RaylibHandle, bindings: &Bindings) -> VictoryAction {
         let m = rl.get_mouse_position();
+        let vp = Viewport::of(rl);
 
-        let (mx, my, mw, mh) = self.btn_menu_rect();
-        let (rx, ry, rw, rh) = self.btn_restart_rect();
+        let (mx, my, mw, mh) = self.btn_menu_rect(vp);
+        let (rx, ry, rw, rh) = self.btn_restart_rect(vp);
 
         self.hovered_menu = m.x >= mx as f32 && m.x <= (mx+mw) as f32 && m.y >= my as f32 && m.y <= (my+mh) as f32;
         self.hovered_restart = m.x >= rx as f32 && m.x <= (rx+rw) as f32 && m.y >= ry as f32 && m.y <= (ry+rh) as f32;
 
-        if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
-            return VictoryAction::Restart;
+        if self.hovered_menu { self.restart_focused = false; }
+        if self.hovered_restart { self.restart_focused = true; }
+
+        // Teclado/mando: ←/→ (cruceta o stick) eligen botón, aceptar lo activa
+        if bindings.pressed(rl, Action::MenuLeft)  { self.restart_focused = false; }
+        if bindings.pressed(rl, Action::MenuRight) { self.restart_focused = true; }
+        if bindings.pressed(rl, Action::MenuConfirm) {
+            return if self.restart_focused { VictoryAction::Restart } else { VictoryAction::ToMenu };
         }
-        if rl.is_key_pressed(KeyboardKey::KEY_M) {
+        if bindings.pressed(rl, Action::MenuBack) {
             return VictoryAction::ToMenu;
         }
         if rl.is_mouse_button_pressed(MouseButton::MOUSE_BUTTON_LEFT) {
@@ -70,10 +83,13 @@ impl VictoryScreen {
     }
 
     /// Dibujo de la pantalla de victoria. Llamar **dentro** del begin_drawing.
-    pub fn draw(&self, d: &mut RaylibDrawHandle) {
+    pub fn draw(&self, d: &mut RaylibDrawHandle, bindings: &Bindings) {
+        let vp = Viewport::of(d);
+        let sw = vp.w;
+
         // Fondo
         d.draw_rectangle_gradient_v(
-            0, 0, SCREEN_W, SCREEN_H,
+            0, 0, vp.w, vp.h,
             Color::new(20, 40, 20, 255),
             Color::new(50, 90, 50, 255),
         );
@@ -82,24 +98,32 @@ impl VictoryScreen {
         let title = "¡VICTORIA!";
         let fs_title = 56;
         let tw = d.measure_text(title, fs_title);
-        d.draw_text(title, (SCREEN_W - tw)/2 + 2, 100 + 2, fs_title, Color::new(0,0,0,160));
-        d.draw_text(title, (SCREEN_W - tw)/2,     100,     fs_title, Color::WHITE);
+        d.draw_text(title, (sw - tw)/2 + 2, 100 + 2, fs_title, Color::new(0,0,0,160));
+        d.draw_text(title, (sw - tw)/2,     100,     fs_title, Color::WHITE);
 
         // Stats
         let mins = (self.elapsed_secs as u32) / 60;
         let secs = (self.elapsed_secs as u32) % 60;
         let time_txt = format!("Tiempo: {}:{:02}", mins, secs);
-        let kills_txt = format!("Eliminaciones: {}", self.kills);
+        let kills_txt = format!("Eliminaciones: {} ({} a la cabeza)", self.kills, self.headshots);
         let fs_stats = 24;
 
         let tw_time  = d.measure_text(&time_txt, fs_stats);
         let tw_kills = d.measure_text(&kills_txt, fs_stats);
-        d.draw_text(&time_txt,  (SCREEN_W - tw_time)/2,  180, fs_stats, Color::new(235,235,235,240));
-        d.draw_text(&kills_txt, (SCREEN_W - tw_kills)/2, 210, fs_stats, Color::new(235,235,235,240));
+        d.draw_text(&time_txt,  (sw - tw_time)/2,  180, fs_stats, Color::new(235,235,235,240));
+        d.draw_text(&kills_txt, (sw - tw_kills)/2, 210, fs_stats, Color::new(235,235,235,240));
 
         // Botones
-        self.draw_button(d, self.btn_menu_rect(), "MENÚ (M)", self.hovered_menu);
-        self.draw_button(d, self.btn_restart_rect(), "REINICIAR (ENTER)", self.hovered_restart);
+        let menu_txt = format!("MENÚ ({})", bindings.label(Action::MenuBack));
+        self.draw_button(d, self.btn_menu_rect(vp), &menu_txt, !self.restart_focused);
+        self.draw_button(d, self.btn_restart_rect(vp), "REINICIAR", self.restart_focused);
+
+        // Ayuda según los controles activos
+        let hint = bindings.help_text(CHOICE_HELP);
+        let hf = 18;
+        let hw = d.measure_text(&hint, hf);
+        let (_, by, _, bh) = self.btn_restart_rect(vp);
+        d.draw_text(&hint, (sw - hw)/2, by + bh + 14, hf, Color::new(230,230,230,220));
     }
 
     fn draw_button(&self, d: &mut RaylibDrawHandle, rect: (i32,i32,i32,i32), label: &str, hovered: bool) {
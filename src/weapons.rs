@@ -0,0 +1,360 @@
+use std::fs;
+use std::path::Path;
+use rand::Rng;
+
+use crate::types::{Weapon, WeaponType, AmmoType, Rarity, FireMode};
+use crate::consts::BLOOM_MAX_DEFAULT;
+use crate::map::is_reserved_glyph;
+
+/// Arma del catálogo: stats base (rareza Común) + dónde aparece + rutas de sus assets
+pub struct WeaponDef {
+    pub weapon: Weapon,
+    pub glyph: Option<char>,          // letra del mapa que la deja en el suelo
+    pub chest_rarity: Option<Rarity>, // rareza con la que sale en cofres (None = no sale)
+    pub icon: String,                 // textura del pickup
+    pub held: String,                 // textura del arma empuñada
+    pub shot_sound: String,
+    pub reload_sound: String,
+}
+
+/// Todas las armas del juego, en el orden del archivo (`WeaponType` es el índice)
+pub struct WeaponCatalog {
+    defs: Vec<WeaponDef>,
+}
+
+const AMMO_NAMES: [(&str, AmmoType); AmmoType::COUNT] = [
+    ("light", AmmoType::Light),
+    ("medium", AmmoType::Medium),
+    ("heavy", AmmoType::Heavy),
+    ("shell", AmmoType::Shell),
+    ("rocket", AmmoType::Rocket),
+];
+
+const FIRE_MODE_NAMES: [(&str, FireMode); 3] = [
+    ("semi", FireMode::Semi),
+    ("burst", FireMode::Burst),
+    ("auto", FireMode::Auto),
+];
+
+const RARITY_NAMES: [(&str, Rarity); 5] = [
+    ("common", Rarity::Common),
+    ("uncommon", Rarity::Uncommon),
+    ("rare", Rarity::Rare),
+    ("epic", Rarity::Epic),
+    ("legendary", Rarity::Legendary),
+];
+
+/// Claves de una sección `[id]` tal cual vienen del archivo
+struct Section {
+    id: String,
+    line: usize,
+    keys: Vec<(String, String)>,
+}
+
+impl Section {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.keys.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+
+    fn req(&self, key: &str) -> Result<&str, String> {
+        self.get(key).ok_or_else(|| format!("Armas, [{}]: falta '{key}'", self.id))
+    }
+
+    fn value<T: std::str::FromStr>(&self, key: &str, default: Option<T>) -> Result<T, String> {
+        match (self.get(key), default) {
+            (Some(v), _) => v.parse().map_err(|_| format!("Armas, [{}]: valor inválido para '{key}': '{v}'", self.id)),
+            (None, Some(d)) => Ok(d),
+            (None, None) => Err(format!("Armas, [{}]: falta '{key}'", self.id)),
+        }
+    }
+
+    fn lookup<T: Copy>(&self, key: &str, table: &[(&str, T)]) -> Result<Option<T>, String> {
+        let Some(v) = self.get(key) else { return Ok(None) };
+        table.iter().find(|(n, _)| n.eq_ignore_ascii_case(v)).map(|&(_, t)| Some(t))
+            .ok_or_else(|| format!("Armas, [{}]: valor desconocido para '{key}': '{v}'", self.id))
+    }
+}
+
+impl WeaponCatalog {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let txt = fs::read_to_string(&path).map_err(|e| format!("No se pudo leer el catálogo de armas: {e}"))?;
+        Self::parse(&txt)
+    }
+
+    /// Catálogo a partir del texto del archivo (`[id]` + `clave = valor`)
+    fn parse(txt: &str) -> Result<Self, String> {
+        let mut sections: Vec<Section> = Vec::new();
+        for (n, line) in txt.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() { continue; }
+
+            if let Some(id) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                sections.push(Section { id: id.trim().to_string(), line: n + 1, keys: Vec::new() });
+                continue;
+            }
+            let (k, v) = line.split_once('=')
+                .ok_or_else(|| format!("Armas, línea {}: falta '='", n + 1))?;
+            let sec = sections.last_mut()
+                .ok_or_else(|| format!("Armas, línea {}: clave fuera de una sección [id]", n + 1))?;
+            sec.keys.push((k.trim().to_string(), v.trim().to_string()));
+        }
+        if sections.is_empty() { return Err("El catálogo de armas está vacío".into()); }
+
+        let mut defs = Vec::with_capacity(sections.len());
+        for (i, sec) in sections.iter().enumerate() {
+            if sections[..i].iter().any(|s| s.id == sec.id) {
+                return Err(format!("Armas, línea {}: [{}] repetida", sec.line, sec.id));
+            }
+            let def = Self::parse_def(sec, WeaponType(i))?;
+            // dos armas con la misma letra: sólo saldría la primera
+            if let Some(g) = def.glyph
+                && let Some(other) = defs.iter().position(|d: &WeaponDef| d.glyph == Some(g))
+            {
+                return Err(format!("Armas, [{}]: glyph '{g}' repetido (ya lo usa [{}])", sec.id, sections[other].id));
+            }
+            defs.push(def);
+        }
+        Ok(Self { defs })
+    }
+
+    fn parse_def(sec: &Section, wtype: WeaponType) -> Result<WeaponDef, String> {
+        const KNOWN: [&str; 26] = [
+            "name", "short", "damage", "fire_interval", "mag_size", "reload_time", "ammo",
+            "pellets", "spread", "bloom", "bloom_max", "move_spread", "recoil", "move_mult", "draw_time", "spin_up", "projectile",
+            "modes", "burst", "burst_interval", "glyph", "chest", "icon", "held", "shot_sound", "reload_sound",
+        ];
+        if let Some((k, _)) = sec.keys.iter().find(|(k, _)| !KNOWN.contains(&k.as_str())) {
+            return Err(format!("Armas, [{}]: clave desconocida '{k}'", sec.id));
+        }
+
+        // Los nombres y los modos viven toda la partida: se filtran una vez al cargar para que `Weapon` siga siendo Copy
+        let leak = |s: &str| -> &'static str { Box::leak(s.to_string().into_boxed_str()) };
+
+        // modos de disparo: lista separada por comas, el primero es el de fábrica
+        let mut modes = Vec::new();
+        for m in sec.get("modes").unwrap_or("auto").split(',') {
+            let mode = FIRE_MODE_NAMES.iter().find(|(n, _)| n.eq_ignore_ascii_case(m.trim())).map(|&(_, f)| f)
+                .ok_or_else(|| format!("Armas, [{}]: modo de disparo desconocido: '{}'", sec.id, m.trim()))?;
+            if !modes.contains(&mode) { modes.push(mode); }
+        }
+
+        let fire_interval: f32 = sec.value("fire_interval", None)?;
+        let bloom: f32 = sec.value("bloom", Some(0.0))?;
+        let weapon = Weapon {
+            wtype,
+            name: leak(sec.req("name")?),
+            short: leak(sec.req("short")?),
+            damage: sec.value("damage", None)?,
+            fire_interval,
+            mag_size: sec.value("mag_size", None)?,
+            reload_time: sec.value("reload_time", None)?,
+            ammo_type: sec.lookup("ammo", &AMMO_NAMES)?
+                .ok_or_else(|| format!("Armas, [{}]: falta 'ammo'", sec.id))?,
+            rarity: Rarity::Common,
+            pellets: sec.value("pellets", Some(1))?,
+            spread: sec.value("spread", Some(0.0))?,
+            bloom,
+            bloom_max: sec.value("bloom_max", Some(bloom * BLOOM_MAX_DEFAULT))?,
+            move_spread: sec.value("move_spread", Some(0.0))?,
+            recoil: sec.value("recoil", Some(0.25))?,
+            move_mult: sec.value("move_mult", Some(1.0))?,
+            draw_time: sec.value("draw_time", Some(0.4))?,
+            spin_up: sec.value("spin_up", Some(0.0))?,
+            projectile: sec.value("projectile", Some(false))?,
+            fire_modes: Box::leak(modes.into_boxed_slice()),
+            burst: sec.value("burst", Some(3))?,
+            burst_interval: sec.value("burst_interval", Some(fire_interval))?,
+        };
+        if weapon.pellets == 0 || weapon.mag_size <= 0 || weapon.fire_interval <= 0.0 || weapon.burst == 0 {
+            return Err(format!("Armas, [{}]: pellets, mag_size, fire_interval y burst deben ser > 0", sec.id));
+        }
+        // el resto puede ser 0 (draw_time = 0: se empuña al instante), pero no negativo
+        let non_negative = [
+            ("damage", weapon.damage as f32), ("reload_time", weapon.reload_time),
+            ("spread", weapon.spread), ("bloom", weapon.bloom), ("bloom_max", weapon.bloom_max),
+            ("move_spread", weapon.move_spread), ("recoil", weapon.recoil), ("move_mult", weapon.move_mult),
+            ("draw_time", weapon.draw_time), ("spin_up", weapon.spin_up),
+            ("burst_interval", weapon.burst_interval),
+        ];
+        if let Some((k, _)) = non_negative.iter().find(|(_, v)| v.is_nan() || *v < 0.0) {
+            return Err(format!("Armas, [{}]: '{k}' no puede ser negativo", sec.id));
+        }
+        // con bloom_max = 0 el cono nunca se abriría: el bloom quedaría anulado sin avisar
+        if weapon.bloom > 0.0 && weapon.bloom_max <= 0.0 {
+            return Err(format!("Armas, [{}]: 'bloom_max' debe ser > 0 si hay 'bloom'", sec.id));
+        }
+
+        let glyph = match sec.get("glyph") {
+            None => None,
+            Some(g) => {
+                let mut it = g.chars();
+                match (it.next(), it.next()) {
+                    // el mapa sólo anota letras/números que no tengan ya otro significado
+                    (Some(c), None) if is_reserved_glyph(c) => {
+                        return Err(format!("Armas, [{}]: glyph '{c}' ya lo usa el mapa", sec.id));
+                    }
+                    (Some(c), None) if c.is_alphanumeric() => Some(c),
+                    _ => return Err(format!("Armas, [{}]: 'glyph' debe ser una sola letra", sec.id)),
+                }
+            }
+        };
+
+        Ok(WeaponDef {
+            weapon,
+            glyph,
+            chest_rarity: sec.lookup("chest", &RARITY_NAMES)?,
+            icon: sec.req("icon")?.to_string(),
+            held: sec.req("held")?.to_string(),
+            shot_sound: sec.req("shot_sound")?.to_string(),
+            reload_sound: sec.req("reload_sound")?.to_string(),
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WeaponDef> {
+        self.defs.iter()
+    }
+
+    /// Stats base (rareza Común) del arma `wtype`
+    pub fn base(&self, wtype: WeaponType) -> Weapon {
+        self.defs[wtype.0].weapon
+    }
+
+    /// Arma con la que empieza el jugador (la primera del archivo)
+    pub fn starting(&self) -> Weapon {
+        self.defs[0].weapon
+    }
+
+    /// Arma que deja en el suelo la letra `ch` del mapa
+    pub fn by_glyph(&self, ch: char) -> Option<WeaponType> {
+        self.defs.iter().position(|d| d.glyph == Some(ch)).map(WeaponType)
+    }
+
+    /// Una cualquiera, misma probabilidad para todas
+    pub fn random<R: Rng>(&self, rng: &mut R) -> WeaponType {
+        WeaponType(rng.gen_range(0..self.defs.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consts::WEAPONS_PATH;
+
+    const PISTOL: &str = "\
+name = Pistola
+short = PIS
+damage = 25
+fire_interval = 0.35
+mag_size = 12
+reload_time = 1.2
+ammo = light
+icon = assets/pistol.png
+held = assets/pistol_held.png
+shot_sound = assets/pistol.wav
+reload_sound = assets/reload.wav
+";
+
+    /// Sección `[id]` con las claves mínimas de una pistola más las de `extra`
+    fn section(id: &str, extra: &str) -> String {
+        format!("[{id}]\n{PISTOL}{extra}\n")
+    }
+
+    /// Pistola `[pistol]` con `key = value` (reemplaza la clave si ya estaba)
+    fn pistol_with(key: &str, value: &str) -> String {
+        let prefix = format!("{key} =");
+        let mut txt = String::from("[pistol]\n");
+        for line in PISTOL.lines().filter(|l| !l.starts_with(&prefix)) {
+            txt.push_str(line);
+            txt.push('\n');
+        }
+        txt + &format!("{key} = {value}\n")
+    }
+
+    fn parse_err(txt: &str) -> String {
+        match WeaponCatalog::parse(txt) {
+            Ok(_) => panic!("el catálogo debería fallar:\n{txt}"),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn shipped_catalog_loads() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(WEAPONS_PATH);
+        let cat = WeaponCatalog::load(path).unwrap();
+        assert!(cat.iter().count() > 1);
+        for def in cat.iter() {
+            if let Some(g) = def.glyph {
+                assert!(!is_reserved_glyph(g));
+                assert_eq!(cat.by_glyph(g), Some(def.weapon.wtype));
+            }
+        }
+    }
+
+    #[test]
+    fn minimal_section_uses_defaults() {
+        let cat = WeaponCatalog::parse(&section("pistol", "")).unwrap();
+        let w = cat.starting();
+        assert_eq!(w.pellets, 1);
+        assert_eq!(w.spread, 0.0);
+        assert!(!w.projectile);
+    }
+
+    #[test]
+    fn rejects_duplicate_id() {
+        let txt = section("pistol", "") + &section("pistol", "");
+        assert!(parse_err(&txt).contains("repetida"));
+    }
+
+    #[test]
+    fn rejects_duplicate_glyph() {
+        let txt = section("pistol", "glyph = Q") + &section("smg", "glyph = Q");
+        assert!(parse_err(&txt).contains("glyph 'Q' repetido"));
+    }
+
+    #[test]
+    fn rejects_map_glyphs() {
+        for g in ["r", "P", "X", "N", "G", "1", "V"] {
+            let e = parse_err(&pistol_with("glyph", g));
+            assert!(e.contains("ya lo usa el mapa"), "{g}: {e}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_key() {
+        assert!(parse_err(&pistol_with("dmg", "3")).contains("clave desconocida 'dmg'"));
+    }
+
+    #[test]
+    fn rejects_zero_pellets() {
+        assert!(parse_err(&pistol_with("pellets", "0")).contains("deben ser > 0"));
+    }
+
+    #[test]
+    fn rejects_negative_values() {
+        for key in [
+            "damage", "reload_time", "spread", "bloom", "bloom_max", "move_spread",
+            "recoil", "move_mult", "draw_time", "spin_up", "burst_interval",
+        ] {
+            let e = parse_err(&pistol_with(key, "-1"));
+            assert!(e.contains(&format!("'{key}' no puede ser negativo")), "{key}: {e}");
+        }
+    }
+
+    #[test]
+    fn bloom_max_defaults_from_bloom() {
+        let cat = WeaponCatalog::parse(&pistol_with("bloom", "0.02")).unwrap();
+        assert_eq!(cat.starting().bloom_max, 0.02 * BLOOM_MAX_DEFAULT);
+    }
+
+    #[test]
+    fn rejects_bloom_without_cap() {
+        let txt = pistol_with("bloom", "0.02") + "bloom_max = 0\n";
+        assert!(parse_err(&txt).contains("'bloom_max' debe ser > 0"));
+    }
+
+    #[test]
+    fn allows_zero_draw_time() {
+        let cat = WeaponCatalog::parse(&pistol_with("draw_time", "0")).unwrap();
+        assert_eq!(cat.starting().draw_time, 0.0);
+    }
+}